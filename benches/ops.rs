@@ -0,0 +1,159 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use mutexedstaticvec::copy_vec::MutexedCopyVec;
+use mutexedstaticvec::MutexedStaticVec;
+use tokio::runtime::{Builder, Runtime};
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().build().unwrap()
+}
+
+async fn filled<T, const N: usize>(make: fn(usize) -> T) -> MutexedStaticVec<T, N> {
+    let v = MutexedStaticVec::default();
+    for i in 0..N {
+        v.push(make(i)).await.unwrap();
+    }
+    v
+}
+
+fn bench_type<T: 'static, const N: usize>(c: &mut Criterion, ty: &str, make: fn(usize) -> T) {
+    let rt = runtime();
+
+    c.bench_with_input(BenchmarkId::new(format!("push/{ty}"), N), &N, |b, _| {
+        b.to_async(&rt).iter_custom(|iters| async move {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let mut v = MutexedStaticVec::<T, N>::default();
+                let start = Instant::now();
+                for i in 0..N {
+                    v.push(make(i)).await.unwrap();
+                }
+                total += start.elapsed();
+                v.replace_all(core::iter::empty()).await.unwrap();
+            }
+            total
+        })
+    });
+
+    // one length lock for the whole batch against the push loop above
+    c.bench_with_input(
+        BenchmarkId::new(format!("push_many/{ty}"), N),
+        &N,
+        |b, _| {
+            b.to_async(&rt).iter_custom(|iters| async move {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let mut v = MutexedStaticVec::<T, N>::default();
+                    let start = Instant::now();
+                    v.push_many((0..N).map(make)).await.unwrap();
+                    total += start.elapsed();
+                    v.replace_all(core::iter::empty()).await.unwrap();
+                }
+                total
+            })
+        },
+    );
+
+    c.bench_with_input(
+        BenchmarkId::new(format!("remove_front/{ty}"), N),
+        &N,
+        |b, _| {
+            b.to_async(&rt).iter_custom(|iters| async move {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let mut v = filled::<T, N>(make).await;
+                    let start = Instant::now();
+                    for _ in 0..N {
+                        drop(v.remove(0).await);
+                    }
+                    total += start.elapsed();
+                }
+                total
+            })
+        },
+    );
+
+    c.bench_with_input(
+        BenchmarkId::new(format!("swap_remove_front/{ty}"), N),
+        &N,
+        |b, _| {
+            b.to_async(&rt).iter_custom(|iters| async move {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let mut v = filled::<T, N>(make).await;
+                    let start = Instant::now();
+                    for _ in 0..N {
+                        drop(v.swap_remove(0).await);
+                    }
+                    total += start.elapsed();
+                }
+                total
+            })
+        },
+    );
+
+    let mut v = rt.block_on(filled::<T, N>(make));
+    c.bench_with_input(BenchmarkId::new(format!("iter/{ty}"), N), &N, |b, _| {
+        b.to_async(&rt).iter(|| async {
+            v.iter().await.fold(0usize, |visited, x| {
+                black_box(x);
+                visited + 1
+            })
+        })
+    });
+    rt.block_on(v.replace_all(core::iter::empty())).unwrap();
+}
+
+// MutexedCopyVec against the u64 runs of bench_type, under matching names
+fn copy_vec<const N: usize>(c: &mut Criterion) {
+    let rt = runtime();
+
+    c.bench_with_input(BenchmarkId::new("push/u64/copy", N), &N, |b, _| {
+        b.to_async(&rt).iter_custom(|iters| async move {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let mut v = MutexedCopyVec::<u64, N>::new();
+                let start = Instant::now();
+                for i in 0..N {
+                    v.push(i as u64).await.unwrap();
+                }
+                total += start.elapsed();
+            }
+            total
+        })
+    });
+
+    c.bench_with_input(BenchmarkId::new("remove_front/u64/copy", N), &N, |b, _| {
+        b.to_async(&rt).iter_custom(|iters| async move {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let mut v = MutexedCopyVec::<u64, N>::new();
+                for i in 0..N {
+                    v.push(i as u64).await.unwrap();
+                }
+                let start = Instant::now();
+                for _ in 0..N {
+                    v.remove(0).await;
+                }
+                total += start.elapsed();
+            }
+            total
+        })
+    });
+}
+
+fn ops(c: &mut Criterion) {
+    bench_type::<u8, 16>(c, "u8", |i| i as u8);
+    bench_type::<u8, 256>(c, "u8", |i| i as u8);
+    bench_type::<u64, 16>(c, "u64", |i| i as u64);
+    bench_type::<u64, 256>(c, "u64", |i| i as u64);
+    copy_vec::<16>(c);
+    copy_vec::<256>(c);
+    bench_type::<String, 16>(c, "String", |i| i.to_string());
+    bench_type::<String, 256>(c, "String", |i| i.to_string());
+}
+
+criterion_group!(benches, ops);
+criterion_main!(benches);
@@ -0,0 +1,156 @@
+use core::slice;
+use tokio::sync::Mutex;
+
+use crate::StaticVecError;
+
+// Every slot holds a live T::default() from construction on, so the storage needs neither
+// MaybeUninit nor unsafe. The price is that appending takes &mut self, since writing a slot
+// through &self while readers hold the prefix would need interior mutability, and that new()
+// cannot be const while Default::default() is not callable in a const fn.
+#[derive(Debug)]
+pub struct MutexedCopyVec<T: Copy + Default, const N: usize> {
+    len: Mutex<usize>,
+    data: [T; N],
+}
+
+impl<T: Copy + Default, const N: usize> MutexedCopyVec<T, N> {
+    pub fn new() -> Self {
+        Self {
+            len: 0.into(),
+            data: [T::default(); N],
+        }
+    }
+
+    // the first len elements are T::default()
+    pub fn with_len(len: usize) -> Result<Self, StaticVecError> {
+        if len > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        Ok(Self {
+            len: len.into(),
+            data: [T::default(); N],
+        })
+    }
+
+    pub async fn from_array<const A: usize>(value: [T; A]) -> Self
+    where
+        [(); N - A]:,
+    {
+        let mut data = [T::default(); N];
+        data[..A].copy_from_slice(&value);
+        Self {
+            len: A.into(),
+            data,
+        }
+    }
+
+    pub async fn len(&self) -> usize {
+        *self.len.lock().await
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        *self.len.lock().await == 0
+    }
+
+    pub async fn as_slice(&self) -> &[T] {
+        &self.data[..*self.len.lock().await]
+    }
+
+    pub async fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data[..*self.len.get_mut()]
+    }
+
+    pub async fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().await.iter()
+    }
+
+    pub async fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().await.iter_mut()
+    }
+
+    pub async fn push(&mut self, item: T) -> Result<&T, StaticVecError> {
+        let len = self.len.get_mut();
+        let old_len = *len;
+        if old_len >= N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        *len = old_len + 1;
+        self.data[old_len] = item;
+        Ok(&self.data[old_len])
+    }
+
+    pub async fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), StaticVecError> {
+        let len = self.len.get_mut();
+        let old_len = *len;
+        if old_len + other.len() > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        self.data[old_len..old_len + other.len()].copy_from_slice(other);
+        *len = old_len + other.len();
+        Ok(())
+    }
+
+    pub async fn try_extend_from_iter<I: Iterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), StaticVecError> {
+        let len = self.len.get_mut();
+        for it in iter {
+            if *len >= N {
+                return Err(StaticVecError::CapacityExceeded);
+            }
+            self.data[*len] = it;
+            *len += 1;
+        }
+        Ok(())
+    }
+
+    pub async fn try_extend_from_iter_ref<'a, I: Iterator<Item = &'a T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), StaticVecError>
+    where
+        T: 'a,
+    {
+        self.try_extend_from_iter(iter.copied()).await
+    }
+
+    pub async fn remove(&mut self, index: usize) -> T {
+        let len = self.len.get_mut();
+
+        assert!(*len > 0);
+        assert!(index < *len);
+
+        let ret = self.data[index];
+        self.data.copy_within(index + 1..*len, index);
+        *len -= 1;
+        ret
+    }
+
+    pub async fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len.get_mut();
+
+        assert!(*len > 0);
+        assert!(index < *len);
+
+        *len -= 1;
+        let ret = self.data[index];
+        self.data[index] = self.data[*len];
+        ret
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for MutexedCopyVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Default, const N: usize> From<[T; N]> for MutexedCopyVec<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self {
+            len: N.into(),
+            data: value,
+        }
+    }
+}
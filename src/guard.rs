@@ -0,0 +1,256 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use tokio::sync::MutexGuard;
+
+use crate::{MutexedStaticVec, StaticVecError};
+
+pub struct StaticVecMutGuard<'a, T, const N: usize> {
+    len: MutexGuard<'a, usize>,
+    // reborrowed from the &mut taken by lock_mut(), so nothing else can view the slots
+    vec: &'a MutexedStaticVec<T, N>,
+}
+
+impl<'a, T, const N: usize> StaticVecMutGuard<'a, T, N> {
+    pub(crate) fn new(len: MutexGuard<'a, usize>, vec: &'a MutexedStaticVec<T, N>) -> Self {
+        Self { len, vec }
+    }
+
+    pub fn len(&self) -> usize {
+        *self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= *self.len {
+            return None;
+        }
+        //safe as we ensure that 0..len elements are initialized
+        Some(unsafe { (*self.vec.data.get_unchecked(index).get()).assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= *self.len {
+            return None;
+        }
+        // the caller may write through the reference, so count it as a write
+        self.vec.bump_versions(index..index + 1);
+        //safe as we ensure that 0..len elements are initialized and we hold the length lock
+        Some(unsafe { (*self.vec.data.get_unchecked(index).get()).assume_init_mut() })
+    }
+
+    pub fn push(&mut self, item: T) -> Result<&mut T, StaticVecError> {
+        let old_len = *self.len;
+        if old_len >= N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        let ret = unsafe {
+            let el: &mut MaybeUninit<T> = &mut *self.vec.data.get_unchecked(old_len).get();
+            el.write(item)
+        };
+        *self.len = old_len + 1;
+        self.vec.bump_generation();
+        self.vec.record_len(old_len + 1);
+        self.vec.bump_versions(old_len..old_len + 1);
+
+        Ok(ret)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let len = *self.len;
+        if len == 0 {
+            return None;
+        }
+        *self.len = len - 1;
+        self.vec.bump_generation();
+        self.vec.bump_versions(len - 1..len);
+        Some(unsafe { ptr::read(self.vec.data.get_unchecked(len - 1).get()).assume_init() })
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = *self.len;
+
+        assert!(len > 0);
+        assert!(index < len);
+
+        unsafe {
+            let ptr = UnsafeCell::raw_get(self.vec.data.as_ptr().add(index));
+            let ret = ptr::read(ptr).assume_init();
+            ptr::copy(ptr.add(1), ptr, len - index - 1);
+            *self.len = len - 1;
+            self.vec.bump_generation();
+            self.vec.bump_versions(index..len);
+            ret
+        }
+    }
+}
+
+// tombstoned slots have been moved out and are reclaimed by compact() or when the guard drops
+#[cfg(feature = "lazy-remove")]
+pub struct LazyRemove<'a, T, const N: usize> {
+    vec: &'a mut MutexedStaticVec<T, N>,
+    len: usize,
+    removed: [bool; N],
+    removed_count: usize,
+}
+
+#[cfg(feature = "lazy-remove")]
+impl<'a, T, const N: usize> LazyRemove<'a, T, N> {
+    pub(crate) fn new(vec: &'a mut MutexedStaticVec<T, N>) -> Self {
+        let len = vec.settled_len();
+        // like Vec::drain, the vec looks empty until the guard drops, so leaking the guard
+        // leaks the elements instead of exposing tombstoned slots
+        *vec.len.get_mut() = 0;
+        vec.bump_generation();
+        Self {
+            vec,
+            len,
+            removed: [false; N],
+            removed_count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len - self.removed_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // indices keep referring to the slots seen when the guard was taken or last compacted
+    pub fn is_removed(&self, index: usize) -> bool {
+        self.removed.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len || self.removed[index] {
+            return None;
+        }
+        //safe as index < len and the slot is not tombstoned
+        Some(unsafe { (*self.vec.data.get_unchecked(index).get()).assume_init_ref() })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        //safe as tombstoned slots are filtered out before being dereferenced
+        self.vec.data[..self.len]
+            .iter()
+            .zip(&self.removed)
+            .filter(|(_, &is_removed)| !is_removed)
+            .map(|(slot, _)| unsafe { (*slot.get()).assume_init_ref() })
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len);
+        assert!(!self.removed[index]);
+
+        self.removed[index] = true;
+        self.removed_count += 1;
+        self.vec.bump_versions(index..index + 1);
+        //safe as index < len and the slot is skipped by compaction from now on
+        unsafe { ptr::read(self.vec.data.get_unchecked(index).get()).assume_init() }
+    }
+
+    // shifts the survivors down now, renumbering them, instead of waiting for the drop
+    pub fn compact(&mut self) {
+        if let Some(first) = self.removed[..self.len]
+            .iter()
+            .position(|&is_removed| is_removed)
+        {
+            self.vec.bump_versions(first..self.len);
+        }
+        let ptr = self.vec.data.as_mut_ptr();
+        let mut write = 0;
+        for read in 0..self.len {
+            if core::mem::take(&mut self.removed[read]) {
+                continue;
+            }
+            if read != write {
+                unsafe { ptr::copy_nonoverlapping(ptr.add(read), ptr.add(write), 1) };
+            }
+            write += 1;
+        }
+        self.len = write;
+        self.removed_count = 0;
+    }
+}
+
+#[cfg(feature = "lazy-remove")]
+impl<T, const N: usize> Drop for LazyRemove<'_, T, N> {
+    fn drop(&mut self) {
+        self.compact();
+        *self.vec.len.get_mut() = self.len;
+        self.vec.bump_generation();
+    }
+}
+
+pub struct SpareCapacity<'a, T> {
+    slots: &'a mut [UnsafeCell<MaybeUninit<T>>],
+    #[cfg(feature = "debug-checks")]
+    written: &'a mut [bool],
+}
+
+impl<'a, T> SpareCapacity<'a, T> {
+    pub(crate) fn new(
+        slots: &'a mut [UnsafeCell<MaybeUninit<T>>],
+        #[cfg(feature = "debug-checks")] written: &'a mut [bool],
+    ) -> Self {
+        Self {
+            slots,
+            #[cfg(feature = "debug-checks")]
+            written,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    // the value is only owned by the vec once set_len commits its slot
+    pub fn write(&mut self, offset: usize, value: T) -> &mut T {
+        #[cfg(feature = "debug-checks")]
+        {
+            self.written[offset] = true;
+        }
+        self.slots[offset].get_mut().write(value)
+    }
+}
+
+pub struct PeekMut<'a, T: Clone> {
+    value: &'a mut T,
+    original: T,
+}
+
+impl<'a, T: Clone> PeekMut<'a, T> {
+    pub(crate) fn new(value: &'a mut T) -> Self {
+        let original = value.clone();
+        Self { value, original }
+    }
+
+    // dropping the guard keeps the changes, cancelling restores the value seen on entry
+    pub fn cancel(self) {
+        *self.value = self.original;
+    }
+}
+
+impl<T: Clone> Deref for PeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: Clone> DerefMut for PeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
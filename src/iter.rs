@@ -0,0 +1,60 @@
+use core::mem::MaybeUninit;
+
+pub struct IntoIter<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    pos: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    // callers must ensure that 0..len elements of data are initialized
+    pub(crate) unsafe fn new(data: [MaybeUninit<T>; N], len: usize) -> Self {
+        Self { data, pos: 0, len }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.pos >= self.len {
+            return None;
+        }
+        //safe as pos..len elements are initialized and not yet yielded
+        let ret = unsafe { self.data.get_unchecked(self.pos).assume_init_read() };
+        self.pos += 1;
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+#[cfg(feature = "futures")]
+impl<T, const N: usize> futures_core::Stream for IntoIter<T, N> {
+    type Item = T;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<T>> {
+        //safe as the elements are never pinned, they are moved out by value
+        core::task::Poll::Ready(unsafe { self.get_unchecked_mut() }.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Iterator::size_hint(self)
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        for el in &mut self.data[self.pos..self.len] {
+            unsafe { el.assume_init_drop() };
+        }
+    }
+}
@@ -1,24 +1,83 @@
 #![no_std]
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
-#![feature(generic_arg_infer)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod copy_vec;
+pub mod guard;
+pub mod iter;
 #[allow(clippy::missing_transmute_annotations)]
 pub mod with_locks;
 
 use core::cell::UnsafeCell;
 use core::mem::MaybeUninit;
+#[cfg(any(
+    feature = "debug-checks",
+    feature = "versioned",
+    feature = "high-water"
+))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
 use core::{ptr, slice};
-use tokio::sync::Mutex;
+#[cfg(feature = "lazy-remove")]
+use guard::LazyRemove;
+use guard::{PeekMut, SpareCapacity, StaticVecMutGuard};
+use tokio::sync::{Mutex, MutexGuard};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum StaticVecError {
     CapacityExceeded,
+    OutOfBounds { index: usize, len: usize },
+    DuplicateIndex { index: usize },
+    InvalidByteLength { len: usize },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GetDisjointError {
+    IndexOutOfBounds,
+    OverlappingIndices,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TryFromResultsError<E> {
+    Item(E),
+    CapacityExceeded,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PushIfAbsentError<T> {
+    AlreadyPresent(T),
+    CapacityExceeded(T),
 }
 
 #[derive(Debug)]
 pub struct MutexedStaticVec<T, const N: usize> {
     len: Mutex<usize>,
     data: [UnsafeCell<MaybeUninit<T>>; N],
+    #[cfg(feature = "debug-checks")]
+    generation: AtomicUsize,
+    // bumped by every write, move or drop touching the slot, readable without the length lock
+    #[cfg(feature = "versioned")]
+    versions: [AtomicUsize; N],
+    #[cfg(feature = "high-water")]
+    max_len: AtomicUsize,
+    // spare slots written since the last spare_capacity_mut(), checked by set_len
+    #[cfg(feature = "debug-checks")]
+    spare_written: [bool; N],
+    // only accessed while the length lock is held
+    #[cfg(feature = "lazy-remove")]
+    tombstones: UnsafeCell<Tombstones<N>>,
+}
+
+// slots in 0..len moved out by remove() whose shift is still pending
+#[cfg(feature = "lazy-remove")]
+#[derive(Debug)]
+struct Tombstones<const N: usize> {
+    removed: [bool; N],
+    count: usize,
 }
 
 fn extend_array<T, const A: usize, const N: usize>(a: [T; A]) -> [UnsafeCell<MaybeUninit<T>>; N]
@@ -34,84 +93,994 @@ where
     ary
 }
 
+struct AssertLenFits<const L: usize, const N: usize>;
+
+impl<const L: usize, const N: usize> AssertLenFits<L, N> {
+    const OK: () = assert!(L <= N, "initial length exceeds capacity");
+}
+
+// commits the number of written elements even if the writing loop unwinds
+struct SetLenOnDrop<'a> {
+    len: &'a mut usize,
+    local_len: usize,
+}
+
+impl<'a> SetLenOnDrop<'a> {
+    fn new(len: &'a mut usize) -> Self {
+        let local_len = *len;
+        Self { len, local_len }
+    }
+}
+
+impl Drop for SetLenOnDrop<'_> {
+    fn drop(&mut self) {
+        *self.len = self.local_len;
+    }
+}
+
+// callers must ensure every slot in `slots` is initialized
+unsafe fn drop_slots<T>(slots: &mut [UnsafeCell<MaybeUninit<T>>]) {
+    for el in slots {
+        el.get_mut().assume_init_drop();
+    }
+}
+
+// callers must ensure no slot in `slots` holds a live element; the bytes are written one by
+// one and volatile, so padding is scrubbed too and the stores are never elided as dead
+#[cfg(feature = "bytemuck")]
+unsafe fn zero_slots<T>(slots: &mut [UnsafeCell<MaybeUninit<T>>]) {
+    let bytes = slots.as_mut_ptr() as *mut u8;
+    for i in 0..core::mem::size_of_val(slots) {
+        ptr::write_volatile(bytes.add(i), 0);
+    }
+}
+
+// callers must ensure every slot in `slots` is initialized
+unsafe fn init_slots_mut<T>(slots: &mut [UnsafeCell<MaybeUninit<T>>]) -> &mut [T] {
+    core::mem::transmute::<&mut [UnsafeCell<MaybeUninit<T>>], &mut [T]>(slots)
+}
+
+// locks two lengths in address order so opposite-direction pairwise calls cannot deadlock
+async fn lock_pair<'a>(
+    a: &'a Mutex<usize>,
+    b: &'a Mutex<usize>,
+) -> (MutexGuard<'a, usize>, MutexGuard<'a, usize>) {
+    if ptr::from_ref(a) < ptr::from_ref(b) {
+        let a_locked = a.lock().await;
+        (a_locked, b.lock().await)
+    } else {
+        let b_locked = b.lock().await;
+        (a.lock().await, b_locked)
+    }
+}
+
 impl<T, const N: usize> MutexedStaticVec<T, N> {
+    // ptr::copy and as_mut_ptr().add() require the backing array to fit in isize::MAX bytes;
+    // zero-sized T needs no special casing as offsets and copies of ZSTs move no bytes, so only
+    // the length changes
+    const FITS_ISIZE: () = assert!(
+        match N.checked_mul(core::mem::size_of::<T>()) {
+            Some(bytes) => bytes <= isize::MAX as usize,
+            None => false,
+        },
+        "backing array exceeds isize::MAX bytes"
+    );
+
+    fn from_parts(data: [UnsafeCell<MaybeUninit<T>>; N], len: usize) -> Self {
+        let () = Self::FITS_ISIZE;
+        Self {
+            len: len.into(),
+            data,
+            #[cfg(feature = "debug-checks")]
+            generation: AtomicUsize::new(0),
+            #[cfg(feature = "versioned")]
+            versions: core::array::from_fn(|_| AtomicUsize::new(0)),
+            #[cfg(feature = "high-water")]
+            max_len: AtomicUsize::new(len),
+            #[cfg(feature = "debug-checks")]
+            spare_written: [false; N],
+            #[cfg(feature = "lazy-remove")]
+            tombstones: UnsafeCell::new(Tombstones {
+                removed: [false; N],
+                count: 0,
+            }),
+        }
+    }
+
     pub fn new(len: usize) -> Result<Self, StaticVecError> {
         if len > N {
             return Err(StaticVecError::CapacityExceeded);
         }
-        Ok(Self {
-            data: core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
-            len: len.into(),
-        })
+        Ok(Self::from_parts(
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            len,
+        ))
+    }
+
+    pub async fn repeat(value: T, count: usize) -> Result<Self, StaticVecError>
+    where
+        T: Clone,
+    {
+        if count > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        let data = core::array::from_fn(|i| {
+            if i < count {
+                MaybeUninit::new(value.clone()).into()
+            } else {
+                UnsafeCell::new(MaybeUninit::uninit())
+            }
+        });
+        Ok(Self::from_parts(data, count))
+    }
+
+    // L <= N is checked when the call is monomorphized, so a too-long L fails to build without
+    // callers needing generic_const_exprs for a `[(); N - L]:` bound
+    pub fn new_const_len<const L: usize>() -> Self
+    where
+        T: Default,
+    {
+        let () = AssertLenFits::<L, N>::OK;
+        let data = core::array::from_fn(|i| {
+            if i < L {
+                MaybeUninit::new(T::default()).into()
+            } else {
+                UnsafeCell::new(MaybeUninit::uninit())
+            }
+        });
+        Self::from_parts(data, L)
+    }
+
+    fn bump_generation(&self) {
+        #[cfg(feature = "debug-checks")]
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    // compacts the slots tombstoned by remove(), callers must hold the length lock guarding len.
+    // Moving elements through &self is sound here: tombstones are only added through &mut self
+    // and every view is built after settling, so no view can overlap a pending compaction
+    #[allow(unused_variables)]
+    fn settle(&self, len: &mut usize) {
+        #[cfg(feature = "lazy-remove")]
+        {
+            let tombstones = unsafe { &mut *self.tombstones.get() };
+            if tombstones.count == 0 {
+                return;
+            }
+
+            let first = tombstones.removed[..*len]
+                .iter()
+                .position(|&is_removed| is_removed)
+                .unwrap_or(*len);
+            let ptr = UnsafeCell::raw_get(self.data.as_ptr());
+            let mut write = 0;
+            for read in 0..*len {
+                if core::mem::take(&mut tombstones.removed[read]) {
+                    continue;
+                }
+                if read != write {
+                    unsafe { ptr::copy_nonoverlapping(ptr.add(read), ptr.add(write), 1) };
+                }
+                write += 1;
+            }
+            tombstones.count = 0;
+            self.bump_versions(first..*len);
+            *len = write;
+            self.bump_generation();
+        }
+    }
+
+    // every path touching the slots locks through here, so it never sees a tombstone
+    async fn lock_settled(&self) -> MutexGuard<'_, usize> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        len_locked
+    }
+
+    // len minus the tombstones, for the methods that only report the length and so need not
+    // pay for a compaction; callers must hold the length lock guarding len
+    fn live_len(&self, len: usize) -> usize {
+        //safe as callers hold the length lock
+        #[cfg(feature = "lazy-remove")]
+        {
+            len - unsafe { (*self.tombstones.get()).count }
+        }
+        #[cfg(not(feature = "lazy-remove"))]
+        len
+    }
+
+    fn settled_len(&mut self) -> usize {
+        let mut len = *self.len.get_mut();
+        self.settle(&mut len);
+        *self.len.get_mut() = len;
+        len
+    }
+
+    #[allow(unused_variables)]
+    fn bump_versions(&self, range: core::ops::Range<usize>) {
+        #[cfg(feature = "versioned")]
+        for version in &self.versions[range] {
+            version.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    #[cfg(feature = "versioned")]
+    pub fn version(&self, index: usize) -> usize {
+        self.versions[index].load(Ordering::Acquire)
+    }
+
+    // compare the returned version against version(index) later to detect writes in between
+    #[cfg(feature = "versioned")]
+    pub async fn read_versioned(&self, index: usize) -> Result<(T, usize), StaticVecError>
+    where
+        T: Clone,
+    {
+        let len_locked = self.lock_settled().await;
+        let len = *len_locked;
+        if index >= len {
+            return Err(StaticVecError::OutOfBounds { index, len });
+        }
+        Ok((self.prefix(len)[index].clone(), self.version(index)))
+    }
+
+    // must be called after every length increase
+    #[allow(unused_variables)]
+    fn record_len(&self, len: usize) {
+        #[cfg(feature = "high-water")]
+        self.max_len.fetch_max(len, Ordering::AcqRel);
+    }
+
+    #[cfg(feature = "high-water")]
+    pub async fn high_water_mark(&self) -> usize {
+        self.max_len.load(Ordering::Acquire)
+    }
+
+    #[cfg(feature = "debug-checks")]
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    // snapshot generation() before taking a view and call this once done with it
+    #[cfg(feature = "debug-checks")]
+    pub fn assert_generation(&self, expected: usize) {
+        assert_eq!(
+            self.generation(),
+            expected,
+            "length changed while a borrowed view was in use"
+        );
+    }
+
+    #[cfg(any(test, feature = "debug-checks"))]
+    pub async fn assert_invariants(&self) {
+        let len = *self.len.lock().await;
+        assert!(len <= N, "length {len} exceeds capacity {N}");
+        #[cfg(feature = "lazy-remove")]
+        {
+            //safe as the length lock is held
+            let tombstones = unsafe { &*self.tombstones.get() };
+            let flagged = tombstones.removed[..len].iter().filter(|&&r| r).count();
+            assert_eq!(flagged, tombstones.count, "tombstone count is out of sync");
+            assert!(
+                !tombstones.removed[len..].contains(&true),
+                "tombstone past length {len}"
+            );
+        }
+        #[cfg(feature = "high-water")]
+        {
+            let max_len = self.max_len.load(Ordering::Acquire);
+            assert!(
+                max_len >= len,
+                "high-water mark {max_len} below length {len}"
+            );
+        }
+    }
+
+    pub async fn assert_len_le(&self, max: usize) {
+        let len = self.live_len(*self.len.lock().await);
+        assert!(len <= max, "length {len} exceeds bound {max}");
     }
 
     pub async fn len(&self) -> usize {
-        *self.len.lock().await
+        self.live_len(*self.len.lock().await)
     }
 
     pub async fn is_empty(&self) -> bool {
-        *self.len.lock().await == 0
+        self.live_len(*self.len.lock().await) == 0
+    }
+
+    pub async fn stats(&self) -> (usize, usize, usize) {
+        let len = self.live_len(*self.len.lock().await);
+        (len, N - len, N)
+    }
+
+    pub const fn size_bytes() -> usize {
+        N * core::mem::size_of::<T>()
+    }
+
+    pub async fn used_bytes(&self) -> usize {
+        self.live_len(*self.len.lock().await) * core::mem::size_of::<T>()
+    }
+
+    // a zero-capacity vec is reported as empty rather than NaN
+    pub async fn utilization(&self) -> f32 {
+        if N == 0 {
+            return 0.0;
+        }
+        self.live_len(*self.len.lock().await) as f32 / N as f32
+    }
+
+    pub async fn next_free(&self) -> Option<usize> {
+        let len = self.live_len(*self.len.lock().await);
+        (len < N).then_some(len)
+    }
+
+    pub async fn ensure_capacity(&self, additional: usize) -> Result<(), StaticVecError> {
+        if additional > N - self.live_len(*self.len.lock().await) {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        Ok(())
     }
 
     pub async fn as_slice(&self) -> &[T] {
+        self.read_prefix().await
+    }
+
+    pub async fn limit(&self, max: usize) -> &[T] {
+        let items = self.read_prefix().await;
+        &items[..items.len().min(max)]
+    }
+
+    pub async fn as_mut_slice(&mut self) -> &mut [T] {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        // the vec is held exclusively until the slice is dropped, so bumping up front is enough
+        self.bump_versions(0..*len_locked);
         //safe as we ensure that 0..len elements are initialized
         unsafe {
-            core::mem::transmute::<&[core::cell::UnsafeCell<core::mem::MaybeUninit<T>>], &[T]>(
-                &self.data[..*self.len.lock().await],
+            core::mem::transmute::<&mut [core::cell::UnsafeCell<core::mem::MaybeUninit<T>>], &mut [T]>(
+                &mut self.data[..*len_locked],
             )
         }
     }
 
-    pub async fn as_mut_slice(&mut self) -> &mut [T] {
-        //safe as we ensure that 0..len elements are initialized
+    fn prefix(&self, len: usize) -> &[T] {
+        //safe as callers pass a len read under the length lock, so 0..len elements are initialized
         unsafe {
-            core::mem::transmute::<&mut [core::cell::UnsafeCell<core::mem::MaybeUninit<T>>], &mut [T]>(
-                &mut self.data[..*self.len.lock().await],
+            core::mem::transmute::<&[core::cell::UnsafeCell<core::mem::MaybeUninit<T>>], &[T]>(
+                &self.data[..len],
             )
         }
     }
 
+    // the common read path: with debug-checks, asserts that no length write landed between
+    // taking the length and building the view from it
+    async fn read_prefix(&self) -> &[T] {
+        let len_locked = self.lock_settled().await;
+        self.prefix(*len_locked)
+    }
+
+    pub async fn is_valid_index(&self, index: usize) -> bool {
+        index < self.live_len(*self.len.lock().await)
+    }
+
+    pub async fn index_checked(&self, index: usize) -> Result<&T, StaticVecError> {
+        let items = self.read_prefix().await;
+        items.get(index).ok_or(StaticVecError::OutOfBounds {
+            index,
+            len: items.len(),
+        })
+    }
+
+    pub async fn as_array(&self) -> Option<&[T; N]> {
+        self.read_prefix().await.try_into().ok()
+    }
+
+    pub async fn copy_window<const W: usize>(&self, start: usize) -> Option<[T; W]>
+    where
+        T: Copy,
+    {
+        self.read_prefix()
+            .await
+            .get(start..)?
+            .first_chunk()
+            .copied()
+    }
+
+    pub async fn split_first(&self) -> Option<(&T, &[T])> {
+        self.read_prefix().await.split_first()
+    }
+
+    pub async fn split_last(&self) -> Option<(&T, &[T])> {
+        self.read_prefix().await.split_last()
+    }
+
+    #[cfg(feature = "bytemuck")]
+    pub async fn as_bytes(&self) -> &[u8]
+    where
+        T: bytemuck::Pod,
+    {
+        bytemuck::cast_slice(self.read_prefix().await)
+    }
+
+    pub async fn for_each_rev<F: FnMut(&T)>(&self, f: F) {
+        let len_locked = self.lock_settled().await;
+        self.prefix(*len_locked).iter().rev().for_each(f);
+    }
+
+    pub async fn eq_slice(&self, other: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.read_prefix().await == other
+    }
+
+    pub async fn max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.read_prefix().await.iter().max()
+    }
+
+    pub async fn min(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.read_prefix().await.iter().min()
+    }
+
+    pub async fn max_by_key<B: Ord, F: FnMut(&T) -> B>(&self, mut f: F) -> Option<&T> {
+        self.read_prefix().await.iter().max_by_key(|x| f(x))
+    }
+
+    pub async fn min_by_key<B: Ord, F: FnMut(&T) -> B>(&self, mut f: F) -> Option<&T> {
+        self.read_prefix().await.iter().min_by_key(|x| f(x))
+    }
+
+    pub async fn position_max(&self) -> Option<usize>
+    where
+        T: Ord,
+    {
+        // max_by_key keeps the last of equal maxima, so scan backwards to report the first
+        self.read_prefix()
+            .await
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, x)| x)
+            .map(|(i, _)| i)
+    }
+
+    pub async fn position_min(&self) -> Option<usize>
+    where
+        T: Ord,
+    {
+        self.read_prefix()
+            .await
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, x)| x)
+            .map(|(i, _)| i)
+    }
+
+    pub async fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.read_prefix().await.iter().fold(init, f)
+    }
+
+    pub async fn sum(&self) -> T
+    where
+        T: core::iter::Sum + Copy,
+    {
+        self.read_prefix().await.iter().copied().sum()
+    }
+
+    pub async fn sum_by<B: core::iter::Sum, F: FnMut(&T) -> B>(&self, f: F) -> B {
+        self.read_prefix().await.iter().map(f).sum()
+    }
+
+    #[cfg(feature = "heapless")]
+    pub async fn to_heapless(&self) -> heapless::Vec<T, N>
+    where
+        T: Clone,
+    {
+        self.read_prefix().await.iter().cloned().collect()
+    }
+
+    pub async fn to_cloned_vec(&self) -> iter::IntoIter<T, N>
+    where
+        T: Clone,
+    {
+        let len_locked = self.lock_settled().await;
+        let mut data: [MaybeUninit<T>; N] = core::array::from_fn(|_| MaybeUninit::uninit());
+        for (slot, item) in data.iter_mut().zip(self.prefix(*len_locked)) {
+            slot.write(item.clone());
+        }
+        unsafe { iter::IntoIter::new(data, *len_locked) }
+    }
+
+    pub async fn reversed(&self) -> MutexedStaticVec<T, N>
+    where
+        T: Clone,
+    {
+        let len_locked = self.lock_settled().await;
+        let items = self.prefix(*len_locked);
+        let data = core::array::from_fn(|i| match items.len().checked_sub(i + 1) {
+            Some(src) => MaybeUninit::new(items[src].clone()).into(),
+            None => UnsafeCell::new(MaybeUninit::uninit()),
+        });
+        Self::from_parts(data, items.len())
+    }
+
+    pub async fn prefix_sums(&self) -> MutexedStaticVec<T, N>
+    where
+        T: core::ops::Add<Output = T> + Copy + Default,
+    {
+        let len_locked = self.lock_settled().await;
+        let items = self.prefix(*len_locked);
+        let mut acc = T::default();
+        let data = core::array::from_fn(|i| match items.get(i) {
+            Some(&item) => {
+                acc = acc + item;
+                MaybeUninit::new(acc).into()
+            }
+            None => UnsafeCell::new(MaybeUninit::uninit()),
+        });
+        Self::from_parts(data, items.len())
+    }
+
+    pub async fn gather(&self, indices: &[usize]) -> Result<MutexedStaticVec<T, N>, StaticVecError>
+    where
+        T: Clone,
+    {
+        let len_locked = self.lock_settled().await;
+        let len = *len_locked;
+        if let Some(&index) = indices.iter().find(|&&index| index >= len) {
+            return Err(StaticVecError::OutOfBounds { index, len });
+        }
+        if indices.len() > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+
+        let items = self.prefix(len);
+        Self::try_from_iter(indices.iter().map(|&index| items[index].clone())).await
+    }
+
+    pub async fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.read_prefix().await.is_sorted()
+    }
+
+    pub async fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, f: F) -> bool {
+        self.read_prefix().await.is_sorted_by(f)
+    }
+
+    pub async fn is_sorted_by_key<K: PartialOrd, F: FnMut(&T) -> K>(&self, f: F) -> bool {
+        self.read_prefix().await.is_sorted_by_key(f)
+    }
+
+    pub async fn chunks_exact(&self, size: usize) -> (slice::ChunksExact<'_, T>, &[T]) {
+        let chunks = self.read_prefix().await.chunks_exact(size);
+        let remainder = chunks.remainder();
+        (chunks, remainder)
+    }
+
+    pub async fn is_disjoint(&self, other: &MutexedStaticVec<T, N>) -> bool
+    where
+        T: PartialEq,
+    {
+        if ptr::eq(self, other) {
+            return self.is_empty().await;
+        }
+        let (mut len_locked, mut other_len_locked) = lock_pair(&self.len, &other.len).await;
+        self.settle(&mut len_locked);
+        other.settle(&mut other_len_locked);
+
+        let theirs = other.prefix(*other_len_locked);
+        !self.prefix(*len_locked).iter().any(|x| theirs.contains(x))
+    }
+
+    pub async fn eq_unordered(&self, other: &MutexedStaticVec<T, N>) -> bool
+    where
+        T: Ord + Clone,
+    {
+        if ptr::eq(self, other) {
+            return true;
+        }
+        let (mut len_locked, mut other_len_locked) = lock_pair(&self.len, &other.len).await;
+        self.settle(&mut len_locked);
+        other.settle(&mut other_len_locked);
+        let ours = self.prefix(*len_locked);
+        let theirs = other.prefix(*other_len_locked);
+        if ours.len() != theirs.len() {
+            return false;
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut ours = ours.to_vec();
+            let mut theirs = theirs.to_vec();
+            ours.sort_unstable();
+            theirs.sort_unstable();
+            ours == theirs
+        }
+        // without an allocator compare the multiplicity of every element instead
+        #[cfg(not(feature = "alloc"))]
+        ours.iter().all(|x| {
+            ours.iter().filter(|y| *y == x).count() == theirs.iter().filter(|y| *y == x).count()
+        })
+    }
+
+    pub async fn find_map<U, F: FnMut(&T) -> Option<U>>(&self, f: F) -> Option<U> {
+        self.read_prefix().await.iter().find_map(f)
+    }
+
+    pub async fn interleave<const M: usize>(
+        &self,
+        other: &MutexedStaticVec<T, N>,
+    ) -> Result<MutexedStaticVec<T, M>, StaticVecError>
+    where
+        T: Clone,
+    {
+        let (ours, theirs, _guards);
+        if ptr::eq(self, other) {
+            let len_locked = self.lock_settled().await;
+            (ours, theirs) = (self.prefix(*len_locked), self.prefix(*len_locked));
+            _guards = (len_locked, None);
+        } else {
+            let (mut len_locked, mut other_len_locked) = lock_pair(&self.len, &other.len).await;
+            self.settle(&mut len_locked);
+            other.settle(&mut other_len_locked);
+            (ours, theirs) = (self.prefix(*len_locked), other.prefix(*other_len_locked));
+            _guards = (len_locked, Some(other_len_locked));
+        }
+        if ours.len() + theirs.len() > M {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+
+        let common = ours.len().min(theirs.len());
+        let mut ret = MutexedStaticVec::default();
+        ret.try_extend_from_iter(
+            ours[..common]
+                .iter()
+                .zip(&theirs[..common])
+                .flat_map(|(a, b)| [a, b])
+                .chain(&ours[common..])
+                .chain(&theirs[common..])
+                .cloned(),
+        )
+        .await?;
+        Ok(ret)
+    }
+
+    pub async fn count_value(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.read_prefix()
+            .await
+            .iter()
+            .filter(|x| *x == value)
+            .count()
+    }
+
+    // index of the first element equal to an earlier one
+    pub async fn first_duplicate(&self) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let items = self.read_prefix().await;
+        (1..items.len()).find(|&i| items[..i].contains(&items[i]))
+    }
+
+    pub async fn has_duplicates(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.first_duplicate().await.is_some()
+    }
+
+    pub async fn take_while<P: FnMut(&T) -> bool>(&self, mut pred: P) -> &[T] {
+        let slice = self.read_prefix().await;
+        let end = slice.iter().position(|x| !pred(x)).unwrap_or(slice.len());
+        &slice[..end]
+    }
+
+    pub async fn skip_while<P: FnMut(&T) -> bool>(&self, mut pred: P) -> &[T] {
+        let slice = self.read_prefix().await;
+        let start = slice.iter().position(|x| !pred(x)).unwrap_or(slice.len());
+        &slice[start..]
+    }
+
+    #[cfg(feature = "crc")]
+    pub async fn crc32(&self) -> u32
+    where
+        T: bytemuck::Pod,
+    {
+        const CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+        CRC32.checksum(self.as_bytes().await)
+    }
+
     pub async fn iter(&self) -> slice::Iter<'_, T> {
+        self.read_prefix().await.iter()
+    }
+
+    pub async fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        self.bump_versions(0..*len_locked);
         //safe as we ensure that 0..len elements are initialized
         unsafe {
             core::mem::transmute::<
-                core::slice::Iter<'_, core::cell::UnsafeCell<core::mem::MaybeUninit<T>>>,
-                core::slice::Iter<'_, T>,
-            >(self.data[..*self.len.lock().await].iter())
+                core::slice::IterMut<'_, core::cell::UnsafeCell<core::mem::MaybeUninit<T>>>,
+                core::slice::IterMut<'_, T>,
+            >(self.data[..*len_locked].iter_mut())
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    pub async fn par_for_each<F: Fn(&T) + Sync + Send>(&self, f: F)
+    where
+        T: Sync,
+    {
+        use rayon::prelude::*;
+
+        let len_locked = self.lock_settled().await;
+        self.prefix(*len_locked).par_iter().for_each(f);
+    }
+
+    // the guard moves elements out and hands out &mut T, so it needs the vec exclusively: no
+    // view borrowed through &self may outlive it
+    pub async fn lock_mut(&mut self) -> StaticVecMutGuard<'_, T, N> {
+        let vec = &*self;
+        StaticVecMutGuard::new(vec.lock_settled().await, vec)
+    }
+
+    // reclaims the slots tombstoned by remove() in one pass; without lazy-remove, remove()
+    // shifts eagerly and there is nothing to do
+    pub async fn compact(&mut self) {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+    }
+
+    #[cfg(feature = "lazy-remove")]
+    pub fn lazy_remove(&mut self) -> LazyRemove<'_, T, N> {
+        LazyRemove::new(self)
+    }
+
+    pub fn spare_capacity_mut(&mut self) -> SpareCapacity<'_, T> {
+        let len = self.settled_len();
+        #[cfg(feature = "debug-checks")]
+        {
+            self.spare_written = [false; N];
+            SpareCapacity::new(&mut self.data[len..], &mut self.spare_written[len..])
+        }
+        #[cfg(not(feature = "debug-checks"))]
+        SpareCapacity::new(&mut self.data[len..])
+    }
+
+    /// # Safety
+    ///
+    /// 0..new_len elements must be initialized, e.g. through spare_capacity_mut().
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        assert!(new_len <= N);
+        self.settled_len();
+        let len = self.len.get_mut();
+        #[cfg(feature = "debug-checks")]
+        for index in *len..new_len {
+            assert!(
+                self.spare_written[index],
+                "set_len commits slot {index} which was never written"
+            );
         }
+        let old_len = core::mem::replace(len, new_len);
+        self.bump_generation();
+        self.record_len(new_len);
+        self.bump_versions(old_len.min(new_len)..old_len.max(new_len));
+    }
+
+    /// # Safety
+    ///
+    /// Writing the length directly skips the capacity check, generation bumps and receiver
+    /// wakeups: any length written through the guard must be <= N with 0..len initialized, e.g.
+    /// through as_ptr(), and slots at or past a lowered length must not be borrowed by views.
+    pub async unsafe fn lock_len(&self) -> MutexGuard<'_, usize> {
+        self.lock_settled().await
+    }
+
+    // slots may only be read or written through this pointer while lock_len() is held
+    pub fn as_ptr(&self) -> *mut T {
+        UnsafeCell::raw_get(self.data.as_ptr()) as *mut T
+    }
+
+    async fn resize_set(&mut self, new_len: usize) {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let old_len = core::mem::replace(&mut *len_locked, new_len);
+        self.bump_generation();
+        self.record_len(new_len);
+        self.bump_versions(old_len.min(new_len)..old_len.max(new_len));
+    }
+
+    pub async fn push(&self, item: T) -> Result<&T, StaticVecError> {
+        let mut len_locked = self.lock_settled().await;
+        let old_len = *len_locked;
+        if old_len >= N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        let ret = unsafe {
+            let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(old_len).get();
+            el.write(item)
+        };
+        *len_locked = old_len + 1;
+        self.bump_generation();
+        self.record_len(old_len + 1);
+        self.bump_versions(old_len..old_len + 1);
+
+        Ok(ret)
+    }
+
+    pub async fn push_many<I: IntoIterator<Item = T>>(
+        &self,
+        iter: I,
+    ) -> Result<usize, StaticVecError> {
+        let mut len_locked = self.lock_settled().await;
+        let old_len = *len_locked;
+        let mut last_item = old_len;
+        let mut ret = Ok(());
+        for it in iter {
+            if last_item >= N {
+                ret = Err(StaticVecError::CapacityExceeded);
+                break;
+            }
+            unsafe {
+                let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(last_item).get();
+                el.write(it);
+            }
+            last_item += 1;
+        }
+        *len_locked = last_item;
+        self.bump_generation();
+        self.record_len(last_item);
+        self.bump_versions(old_len..last_item);
+
+        ret.map(|_| last_item - old_len)
+    }
+
+    pub async fn push_if_absent(&self, item: T) -> Result<&T, PushIfAbsentError<T>>
+    where
+        T: PartialEq,
+    {
+        let mut len_locked = self.lock_settled().await;
+        let old_len = *len_locked;
+        if self.prefix(old_len).contains(&item) {
+            return Err(PushIfAbsentError::AlreadyPresent(item));
+        }
+        if old_len >= N {
+            return Err(PushIfAbsentError::CapacityExceeded(item));
+        }
+        let ret = unsafe {
+            let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(old_len).get();
+            el.write(item)
+        };
+        *len_locked = old_len + 1;
+        self.bump_generation();
+        self.record_len(old_len + 1);
+        self.bump_versions(old_len..old_len + 1);
+
+        Ok(ret)
+    }
+
+    pub async fn push_cloned(&self, item: &T) -> Result<&T, StaticVecError>
+    where
+        T: Clone,
+    {
+        self.push(item.clone()).await
+    }
+
+    pub async fn saturating_push(&self, item: T) -> bool {
+        self.push(item).await.is_ok()
     }
 
-    pub async fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
-        //safe as we ensure that 0..len elements are initialized
-        unsafe {
-            core::mem::transmute::<
-                core::slice::IterMut<'_, core::cell::UnsafeCell<core::mem::MaybeUninit<T>>>,
-                core::slice::IterMut<'_, T>,
-            >(self.data[..*self.len.lock().await].iter_mut())
+    // the front element is moved out and the rest shifted down, so this needs the vec
+    // exclusively; no push can land while it is held, so an empty vec ends the stream
+    pub fn poll_recv(&mut self, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let len = self.settled_len();
+        if len == 0 {
+            return Poll::Ready(None);
         }
+
+        let ret = unsafe {
+            let ptr = self.data.as_mut_ptr();
+            let ret = ptr::read(ptr).into_inner().assume_init();
+            ptr::copy(ptr.add(1), ptr, len - 1);
+            ret
+        };
+        *self.len.get_mut() = len - 1;
+        self.bump_generation();
+        self.bump_versions(0..len);
+        Poll::Ready(Some(ret))
     }
 
-    async fn resize_set(&mut self, new_len: usize) {
-        *self.len.lock().await = new_len;
+    pub async fn update<F: FnOnce(&mut T)>(
+        &mut self,
+        index: usize,
+        f: F,
+    ) -> Result<(), StaticVecError> {
+        let len = self.settled_len();
+        if index >= len {
+            return Err(StaticVecError::OutOfBounds { index, len });
+        }
+        //safe as index < len
+        f(unsafe {
+            self.data
+                .get_unchecked_mut(index)
+                .get_mut()
+                .assume_init_mut()
+        });
+        self.bump_versions(index..index + 1);
+        Ok(())
     }
 
-    pub async fn push(&self, item: T) -> Result<&T, StaticVecError> {
-        let mut len_locked = self.len.lock().await;
-        let old_len = *len_locked;
-        let ret = unsafe {
-            let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(old_len).get();
-            el.write(item)
+    pub async fn replace(&mut self, index: usize, value: T) -> Result<T, StaticVecError> {
+        let len = self.settled_len();
+        if index >= len {
+            return Err(StaticVecError::OutOfBounds { index, len });
+        }
+        //safe as index < len
+        let old = unsafe {
+            let el = self.data.get_unchecked_mut(index).get_mut();
+            core::mem::replace(el, MaybeUninit::new(value)).assume_init()
         };
-        *len_locked = old_len + 1;
+        self.bump_versions(index..index + 1);
+        Ok(old)
+    }
 
+    pub async fn increment(&mut self, index: usize, by: T) -> Result<T, StaticVecError>
+    where
+        T: core::ops::AddAssign + Copy,
+    {
+        let len = self.settled_len();
+        if index >= len {
+            return Err(StaticVecError::OutOfBounds { index, len });
+        }
+        //safe as index < len
+        let el = unsafe {
+            self.data
+                .get_unchecked_mut(index)
+                .get_mut()
+                .assume_init_mut()
+        };
+        *el += by;
+        let ret = *el;
+        self.bump_versions(index..index + 1);
         Ok(ret)
     }
 
+    pub async fn peek_mut(&mut self, index: usize) -> Option<PeekMut<'_, T>>
+    where
+        T: Clone,
+    {
+        let len = self.settled_len();
+        if index >= len {
+            return None;
+        }
+        self.bump_versions(index..index + 1);
+        //safe as index < len
+        Some(PeekMut::new(unsafe {
+            self.data
+                .get_unchecked_mut(index)
+                .get_mut()
+                .assume_init_mut()
+        }))
+    }
+
     pub async fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), StaticVecError>
     where
         T: Copy,
     {
         let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
         let old_len = *len_locked;
         let slice = unsafe {
             core::mem::transmute::<&mut [core::cell::UnsafeCell<core::mem::MaybeUninit<T>>], &mut [T]>(
@@ -120,6 +1089,9 @@ impl<T, const N: usize> MutexedStaticVec<T, N> {
         };
         slice[old_len..].copy_from_slice(other);
         *len_locked = old_len + other.len();
+        self.bump_generation();
+        self.record_len(old_len + other.len());
+        self.bump_versions(old_len..old_len + other.len());
         Ok(())
     }
 
@@ -128,14 +1100,145 @@ impl<T, const N: usize> MutexedStaticVec<T, N> {
         iter: I,
     ) -> Result<(), StaticVecError> {
         let mut len_locked = self.len.lock().await;
-        let mut last_item = *len_locked;
+        self.settle(&mut len_locked);
+        let old_len = *len_locked;
+        let mut ret = Ok(());
+        {
+            let mut len = SetLenOnDrop::new(&mut len_locked);
+            for it in iter {
+                if len.local_len >= N {
+                    ret = Err(StaticVecError::CapacityExceeded);
+                    break;
+                }
+                unsafe {
+                    *self.data.get_unchecked_mut(len.local_len) = MaybeUninit::new(it).into();
+                }
+                len.local_len += 1;
+            }
+        }
+        self.bump_generation();
+        self.record_len(*len_locked);
+        self.bump_versions(old_len..*len_locked);
+        ret
+    }
+
+    pub async fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, StaticVecError> {
+        let mut x = Self::default();
+        x.try_extend_from_iter(iter.into_iter()).await?;
+        Ok(x)
+    }
+
+    pub async fn try_from_results<E, I: IntoIterator<Item = Result<T, E>>>(
+        iter: I,
+    ) -> Result<Self, TryFromResultsError<E>> {
+        let mut x = Self::default();
+        let mut len = 0;
         for it in iter {
-            unsafe {
-                *self.data.get_unchecked_mut(last_item) = MaybeUninit::new(it).into();
+            let err = match it {
+                Ok(_) if len >= N => TryFromResultsError::CapacityExceeded,
+                Ok(item) => {
+                    x.data[len] = MaybeUninit::new(item).into();
+                    len += 1;
+                    continue;
+                }
+                Err(e) => TryFromResultsError::Item(e),
+            };
+            unsafe { drop_slots(&mut x.data[..len]) };
+            return Err(err);
+        }
+        x.resize_set(len).await;
+        Ok(x)
+    }
+
+    #[cfg(feature = "futures")]
+    pub async fn from_stream<S: futures_core::Stream<Item = T> + Unpin>(
+        mut stream: S,
+    ) -> Result<Self, StaticVecError> {
+        let mut x = Self::default();
+        {
+            let mut len = SetLenOnDrop::new(x.len.get_mut());
+            while let Some(item) =
+                core::future::poll_fn(|cx| core::pin::Pin::new(&mut stream).poll_next(cx)).await
+            {
+                if len.local_len >= N {
+                    return Err(StaticVecError::CapacityExceeded);
+                }
+                unsafe {
+                    *x.data.get_unchecked_mut(len.local_len) = MaybeUninit::new(item).into();
+                }
+                len.local_len += 1;
             }
-            last_item += 1;
         }
-        *len_locked = last_item;
+        let len = *x.len.get_mut();
+        x.bump_generation();
+        x.record_len(len);
+        Ok(x)
+    }
+
+    pub async fn replace_all<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), StaticVecError> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let old_len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+        unsafe { drop_slots(&mut self.data[..old_len]) };
+
+        let mut ret = Ok(());
+        {
+            let mut len = SetLenOnDrop::new(&mut len_locked);
+            for it in iter {
+                if len.local_len >= N {
+                    unsafe { drop_slots(&mut self.data[..len.local_len]) };
+                    len.local_len = 0;
+                    ret = Err(StaticVecError::CapacityExceeded);
+                    break;
+                }
+                unsafe {
+                    *self.data.get_unchecked_mut(len.local_len) = MaybeUninit::new(it).into();
+                }
+                len.local_len += 1;
+            }
+        }
+        self.bump_generation();
+        self.record_len(*len_locked);
+        self.bump_versions(0..old_len.max(*len_locked));
+        ret
+    }
+
+    pub async fn resize_with<F: FnMut() -> T>(
+        &mut self,
+        new_len: usize,
+        mut f: F,
+    ) -> Result<(), StaticVecError> {
+        if new_len > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let old_len = *len_locked;
+
+        if new_len <= old_len {
+            *len_locked = new_len;
+            self.bump_generation();
+            self.bump_versions(new_len..old_len);
+            unsafe { drop_slots(&mut self.data[new_len..old_len]) };
+            return Ok(());
+        }
+
+        {
+            let mut len = SetLenOnDrop::new(&mut len_locked);
+            while len.local_len < new_len {
+                unsafe {
+                    *self.data.get_unchecked_mut(len.local_len) = MaybeUninit::new(f()).into();
+                }
+                len.local_len += 1;
+            }
+        }
+        self.bump_generation();
+        self.record_len(new_len);
+        self.bump_versions(old_len..new_len);
         Ok(())
     }
 
@@ -149,6 +1252,204 @@ impl<T, const N: usize> MutexedStaticVec<T, N> {
         self.try_extend_from_iter(iter.cloned()).await
     }
 
+    #[cfg(feature = "bytemuck")]
+    pub async fn from_bytes(bytes: &[u8]) -> Result<Self, StaticVecError>
+    where
+        T: bytemuck::Pod,
+    {
+        let size = core::mem::size_of::<T>();
+        if size == 0 || !bytes.len().is_multiple_of(size) {
+            return Err(StaticVecError::InvalidByteLength { len: bytes.len() });
+        }
+        if bytes.len() / size > N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+
+        let mut x = Self::default();
+        x.try_extend_from_iter(bytes.chunks_exact(size).map(bytemuck::pod_read_unaligned))
+            .await?;
+        Ok(x)
+    }
+
+    #[cfg(feature = "bytemuck")]
+    pub async fn zeroize_spare(&mut self)
+    where
+        T: bytemuck::Zeroable,
+    {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        //safe as the spare slots hold no live elements
+        unsafe { zero_slots(&mut self.data[*len_locked..]) };
+    }
+
+    #[cfg(feature = "bytemuck")]
+    pub async fn zeroize(&mut self)
+    where
+        T: bytemuck::Zeroable,
+    {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+        self.bump_versions(0..len);
+        unsafe {
+            drop_slots(&mut self.data[..len]);
+            zero_slots(&mut self.data);
+        }
+    }
+
+    pub async fn try_from_chunks<const C: usize, I: IntoIterator<Item = [T; C]>>(
+        iter: I,
+    ) -> Result<Self, StaticVecError> {
+        Self::try_from_iter(iter.into_iter().flatten()).await
+    }
+
+    pub async fn take_raw(&mut self) -> ([MaybeUninit<T>; N], usize) {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+        self.bump_versions(0..len);
+        let data = core::mem::replace(
+            &mut self.data,
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+        );
+        (data.map(UnsafeCell::into_inner), len)
+    }
+
+    pub async fn take_all_into<C: Extend<T> + Default>(&mut self) -> C {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        // a panicking Extend leaks the remaining elements instead of exposing moved-out slots
+        let len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+        self.bump_versions(0..len);
+
+        let mut ret = C::default();
+        //safe as 0..len elements were initialized and each is read out exactly once
+        ret.extend(
+            self.data[..len]
+                .iter_mut()
+                .map(|x| unsafe { x.get_mut().assume_init_read() }),
+        );
+        ret
+    }
+
+    fn into_items(mut self) -> iter::IntoIter<T, N> {
+        let len = self.settled_len();
+        //safe as we ensure that 0..len elements are initialized
+        unsafe { iter::IntoIter::new(self.data.map(UnsafeCell::into_inner), len) }
+    }
+
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> impl futures_core::Stream<Item = T> {
+        self.into_items()
+    }
+
+    pub async fn split_into_frames<const F: usize>(
+        self,
+    ) -> impl Iterator<Item = MutexedStaticVec<T, F>> {
+        assert!(F > 0);
+        let mut items = self.into_items();
+
+        core::iter::from_fn(move || {
+            let mut data: [UnsafeCell<MaybeUninit<T>>; F] =
+                core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit()));
+            let mut frame_len = 0;
+            for (slot, item) in data.iter_mut().zip(items.by_ref()) {
+                *slot = MaybeUninit::new(item).into();
+                frame_len += 1;
+            }
+            (frame_len > 0).then(|| MutexedStaticVec::from_parts(data, frame_len))
+        })
+    }
+
+    pub async fn into_array(self) -> Result<[T; N], Self> {
+        if *self.lock_settled().await != N {
+            return Err(self);
+        }
+        //safe as len == N, so every element is initialized
+        Ok(self.data.map(|x| unsafe { x.into_inner().assume_init() }))
+    }
+
+    pub async fn partition<P: FnMut(&T) -> bool>(
+        mut self,
+        mut pred: P,
+    ) -> (MutexedStaticVec<T, N>, MutexedStaticVec<T, N>) {
+        let len = self.settled_len();
+        let mut matching = Self::default();
+        let mut rest = Self::default();
+        let mut matching_len = SetLenOnDrop::new(matching.len.get_mut());
+        let mut rest_len = SetLenOnDrop::new(rest.len.get_mut());
+
+        //safe as we ensure that 0..len elements are initialized
+        for item in self
+            .data
+            .into_iter()
+            .take(len)
+            .map(|x| unsafe { x.into_inner().assume_init() })
+        {
+            let (data, len) = if pred(&item) {
+                (&mut matching.data, &mut matching_len)
+            } else {
+                (&mut rest.data, &mut rest_len)
+            };
+            // both sides have capacity N, so neither can overflow
+            unsafe { *data.get_unchecked_mut(len.local_len) = MaybeUninit::new(item).into() };
+            len.local_len += 1;
+        }
+        drop((matching_len, rest_len));
+        let (matching_len, rest_len) = (*matching.len.get_mut(), *rest.len.get_mut());
+        matching.record_len(matching_len);
+        rest.record_len(rest_len);
+        (matching, rest)
+    }
+
+    pub async fn swap_contents(&mut self, other: &mut MutexedStaticVec<T, N>) {
+        let (mut len_locked, mut other_len_locked) = lock_pair(&self.len, &other.len).await;
+        self.settle(&mut len_locked);
+        other.settle(&mut other_len_locked);
+
+        core::mem::swap(&mut self.data, &mut other.data);
+        core::mem::swap(&mut *len_locked, &mut *other_len_locked);
+        self.bump_generation();
+        other.bump_generation();
+        self.record_len(*len_locked);
+        other.record_len(*other_len_locked);
+        let touched = (*len_locked).max(*other_len_locked);
+        self.bump_versions(0..touched);
+        other.bump_versions(0..touched);
+    }
+
+    pub async fn drain_into<const M: usize>(
+        &mut self,
+        dst: &mut MutexedStaticVec<T, M>,
+        count: usize,
+    ) -> Result<usize, StaticVecError> {
+        let (mut len_locked, mut dst_len_locked) = lock_pair(&self.len, &dst.len).await;
+        self.settle(&mut len_locked);
+        dst.settle(&mut dst_len_locked);
+        let len = *len_locked;
+        let dst_len = *dst_len_locked;
+        let moved = count.min(len).min(M - dst_len);
+
+        unsafe {
+            let src = self.data.as_mut_ptr();
+            ptr::copy_nonoverlapping(src, dst.data.as_mut_ptr().add(dst_len), moved);
+            ptr::copy(src.add(moved), src, len - moved);
+        }
+        *len_locked = len - moved;
+        *dst_len_locked = dst_len + moved;
+        self.bump_generation();
+        dst.bump_generation();
+        dst.record_len(dst_len + moved);
+        if moved > 0 {
+            self.bump_versions(0..len);
+        }
+        dst.bump_versions(dst_len..dst_len + moved);
+        Ok(moved)
+    }
+
     pub async fn from_array<const A: usize>(value: [T; A]) -> Self
     where
         T: Clone,
@@ -159,6 +1460,88 @@ impl<T, const N: usize> MutexedStaticVec<T, N> {
         x
     }
 
+    pub async fn from_array_truncating<const A: usize>(value: [T; A]) -> Self {
+        let mut data: [UnsafeCell<MaybeUninit<T>>; N] =
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit()));
+        let mut len = 0;
+        // elements past N are dropped along with the array iterator
+        for (slot, item) in data.iter_mut().zip(value) {
+            *slot = MaybeUninit::new(item).into();
+            len += 1;
+        }
+        Self::from_parts(data, len)
+    }
+
+    pub async fn from_option_array<const A: usize>(value: [Option<T>; A]) -> Self
+    where
+        [(); N - A]:,
+    {
+        let mut data: [UnsafeCell<MaybeUninit<T>>; N] =
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit()));
+        let mut len = 0;
+        for item in value.into_iter().flatten() {
+            data[len] = MaybeUninit::new(item).into();
+            len += 1;
+        }
+        Self::from_parts(data, len)
+    }
+
+    pub async fn insert_sorted(&mut self, item: T) -> Result<usize, StaticVecError>
+    where
+        T: Ord,
+    {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = *len_locked;
+        if len >= N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+
+        let index = self.prefix(len).partition_point(|x| x <= &item);
+        unsafe {
+            let ptr = self.data.as_mut_ptr().add(index);
+            ptr::copy(ptr, ptr.add(1), len - index);
+            ptr::write(ptr, MaybeUninit::new(item).into());
+        }
+        *len_locked = len + 1;
+        self.bump_generation();
+        self.record_len(len + 1);
+        self.bump_versions(index..len + 1);
+        Ok(index)
+    }
+
+    // with lazy-remove the slot is only tombstoned and the shift is deferred to compact() or
+    // the next method touching the slots; index still counts live elements only
+    #[cfg(feature = "lazy-remove")]
+    pub async fn remove(&mut self, index: usize) -> T {
+        let len_locked = self.len.lock().await;
+        let len = *len_locked;
+        let tombstones = self.tombstones.get_mut();
+
+        assert!(index < len - tombstones.count);
+
+        let slot = if tombstones.count == 0 {
+            index
+        } else {
+            (0..len)
+                .filter(|&i| !tombstones.removed[i])
+                .nth(index)
+                .unwrap()
+        };
+        tombstones.removed[slot] = true;
+        tombstones.count += 1;
+        self.bump_generation();
+        self.bump_versions(slot..slot + 1);
+        //safe as the slot was live and every later access settles past it
+        unsafe {
+            self.data
+                .get_unchecked_mut(slot)
+                .get_mut()
+                .assume_init_read()
+        }
+    }
+
+    #[cfg(not(feature = "lazy-remove"))]
     pub async fn remove(&mut self, index: usize) -> T {
         let mut len_locked = self.len.lock().await;
         let len = *len_locked;
@@ -180,52 +1563,295 @@ impl<T, const N: usize> MutexedStaticVec<T, N> {
                 ptr::copy(ptr.add(1), ptr, len - index - 1);
             }
             *len_locked = len - 1;
+            self.bump_generation();
+            self.bump_versions(index..len);
+            ret
+        }
+    }
+
+    pub async fn remove_if<P: FnOnce(&T) -> bool>(&mut self, index: usize, pred: P) -> Option<T> {
+        let len = self.settled_len();
+
+        assert!(index < len);
+
+        if !pred(&self.prefix(len)[index]) {
+            return None;
+        }
+        Some(self.remove(index).await)
+    }
+
+    pub async fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty().await {
+            return None;
+        }
+        Some(self.remove(0).await)
+    }
+
+    pub async fn swap_remove(&mut self, index: usize) -> T {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = *len_locked;
+
+        assert!(len > 0);
+        assert!(index < len);
+
+        unsafe {
+            let base = self.data.as_mut_ptr();
+            let ret = ptr::read(base.add(index)).into_inner().assume_init();
+            // move the last element into the hole
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            *len_locked = len - 1;
+            self.bump_generation();
+            self.bump_versions(index..len);
             ret
         }
     }
+
+    pub async fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        self.retain_mut(|x| f(x)).await
+    }
+
+    pub async fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) -> usize {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        // a panicking predicate leaks the elements instead of exposing moved-out slots
+        let len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+
+        let ptr = self.data.as_mut_ptr();
+        let mut write = 0;
+        for read in 0..len {
+            unsafe {
+                let el = (*ptr.add(read)).get_mut();
+                if !f(el.assume_init_mut()) {
+                    el.assume_init_drop();
+                    continue;
+                }
+                if read != write {
+                    ptr::copy_nonoverlapping(ptr.add(read), ptr.add(write), 1);
+                }
+            }
+            write += 1;
+        }
+        *len_locked = write;
+        self.bump_generation();
+        // f may have written through any element it was handed
+        self.bump_versions(0..len);
+        len - write
+    }
+
+    pub async fn move_element(&mut self, from: usize, to: usize) {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = *len_locked;
+
+        assert!(from < len);
+        assert!(to < len);
+
+        let slice = unsafe { init_slots_mut(&mut self.data[..len]) };
+        if from < to {
+            slice[from..=to].rotate_left(1);
+        } else {
+            slice[to..=from].rotate_right(1);
+        }
+        self.bump_versions(from.min(to)..from.max(to) + 1);
+    }
+
+    pub async fn cycle(&mut self) -> Option<&T> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        self.bump_versions(0..*len_locked);
+        let slice = unsafe { init_slots_mut(&mut self.data[..*len_locked]) };
+        if slice.is_empty() {
+            return None;
+        }
+        slice.rotate_left(1);
+        slice.last()
+    }
+
+    pub async fn apply_all<F: FnMut(&mut T)>(&mut self, f: F) {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        unsafe { init_slots_mut(&mut self.data[..*len_locked]) }
+            .iter_mut()
+            .for_each(f);
+        self.bump_versions(0..*len_locked);
+    }
+
+    pub async fn try_apply_all<E, F: FnMut(&mut T) -> Result<(), E>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), E> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let ret = unsafe { init_slots_mut(&mut self.data[..*len_locked]) }
+            .iter_mut()
+            .try_for_each(f);
+        // an early error may still follow writes to the elements before it
+        self.bump_versions(0..*len_locked);
+        ret
+    }
+
+    pub async fn get_disjoint_mut<const M: usize>(
+        &mut self,
+        indices: [usize; M],
+    ) -> Result<[&mut T; M], GetDisjointError> {
+        let len = self.settled_len();
+        // the returned borrows outlive this call, so every handed out slot is bumped up front
+        for &index in indices.iter().filter(|&&index| index < len) {
+            self.bump_versions(index..index + 1);
+        }
+        unsafe { init_slots_mut(&mut self.data[..len]) }
+            .get_disjoint_mut(indices)
+            .map_err(|e| match e {
+                slice::GetDisjointMutError::IndexOutOfBounds => GetDisjointError::IndexOutOfBounds,
+                slice::GetDisjointMutError::OverlappingIndices => {
+                    GetDisjointError::OverlappingIndices
+                }
+            })
+    }
+
+    pub async fn remove_indices(&mut self, indices: &[usize]) -> Result<(), StaticVecError> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = *len_locked;
+
+        let mut removed = [false; N];
+        for &index in indices {
+            if index >= len {
+                return Err(StaticVecError::OutOfBounds { index, len });
+            }
+            if removed[index] {
+                return Err(StaticVecError::DuplicateIndex { index });
+            }
+            removed[index] = true;
+        }
+
+        let ptr = self.data.as_mut_ptr();
+        let mut write = 0;
+        for (read, &is_removed) in removed[..len].iter().enumerate() {
+            unsafe {
+                if is_removed {
+                    (*ptr.add(read)).get_mut().assume_init_drop();
+                } else {
+                    if read != write {
+                        ptr::copy_nonoverlapping(ptr.add(read), ptr.add(write), 1);
+                    }
+                    write += 1;
+                }
+            }
+        }
+        *len_locked = write;
+        self.bump_generation();
+        if let Some(&first) = indices.iter().min() {
+            self.bump_versions(first..len);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> MutexedStaticVec<u8, N> {
+    pub async fn fill_from_read<R: std::io::Read>(
+        &mut self,
+        reader: &mut R,
+    ) -> std::io::Result<usize> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let len = *len_locked;
+
+        let spare = &mut self.data[len..];
+        for el in spare.iter_mut() {
+            el.get_mut().write(0);
+        }
+        //safe as every spare slot was just zeroed
+        let buf = unsafe { init_slots_mut(spare) };
+        let read = reader.read(buf)?;
+        assert!(read <= buf.len());
+
+        *len_locked = len + read;
+        self.bump_generation();
+        self.record_len(len + read);
+        self.bump_versions(len..len + read);
+        Ok(read)
+    }
+
+    pub async fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<usize> {
+        let len_locked = self.lock_settled().await;
+        writer.write_all(self.prefix(*len_locked))?;
+        Ok(*len_locked)
+    }
 }
 
 impl<T, const N: usize> Default for MutexedStaticVec<T, N> {
     fn default() -> Self {
-        Self {
-            len: 0.into(),
-            data: core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
-        }
+        Self::from_parts(
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            0,
+        )
     }
 }
 
 impl<'a, T: Clone, const N: usize> From<&'a [T; N]> for MutexedStaticVec<T, N> {
     fn from(value: &'a [T; N]) -> Self {
-        Self {
-            data: value.clone().map(|x| MaybeUninit::new(x).into()),
-            len: N.into(),
-        }
+        Self::from_parts(value.clone().map(|x| MaybeUninit::new(x).into()), N)
     }
 }
 
 impl<T, const N: usize> From<[T; N]> for MutexedStaticVec<T, N> {
     fn from(value: [T; N]) -> Self {
-        Self {
-            data: value.map(|x| MaybeUninit::new(x).into()),
-            len: N.into(),
-        }
+        Self::from_parts(value.map(|x| MaybeUninit::new(x).into()), N)
     }
 }
 
 impl<T, const N: usize> From<[MaybeUninit<T>; N]> for MutexedStaticVec<T, N> {
     fn from(value: [MaybeUninit<T>; N]) -> Self {
-        Self {
-            data: value.map(|x| x.into()),
-            len: N.into(),
-        }
+        Self::from_parts(value.map(|x| x.into()), N)
     }
 }
 
 impl<T, const N: usize> From<[UnsafeCell<MaybeUninit<T>>; N]> for MutexedStaticVec<T, N> {
     fn from(value: [UnsafeCell<MaybeUninit<T>>; N]) -> Self {
-        Self {
-            data: value,
-            len: N.into(),
+        Self::from_parts(value, N)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> From<arrayvec::ArrayVec<T, N>> for MutexedStaticVec<T, N> {
+    fn from(value: arrayvec::ArrayVec<T, N>) -> Self {
+        let len = value.len();
+        let mut data: [UnsafeCell<MaybeUninit<T>>; N] =
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit()));
+        for (slot, item) in data.iter_mut().zip(value) {
+            *slot = MaybeUninit::new(item).into();
+        }
+        Self::from_parts(data, len)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> From<MutexedStaticVec<T, N>> for arrayvec::ArrayVec<T, N> {
+    fn from(mut value: MutexedStaticVec<T, N>) -> Self {
+        let len = value.settled_len();
+        //safe as we ensure that 0..len elements are initialized
+        value
+            .data
+            .into_iter()
+            .take(len)
+            .map(|x| unsafe { x.into_inner().assume_init() })
+            .collect()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> From<heapless::Vec<T, N>> for MutexedStaticVec<T, N> {
+    fn from(value: heapless::Vec<T, N>) -> Self {
+        let len = value.len();
+        let mut data: [UnsafeCell<MaybeUninit<T>>; N] =
+            core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit()));
+        for (slot, item) in data.iter_mut().zip(value) {
+            *slot = MaybeUninit::new(item).into();
         }
+        Self::from_parts(data, len)
     }
 }
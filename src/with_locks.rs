@@ -1,6 +1,6 @@
-use core::{future::Future, mem::MaybeUninit, ops::DerefMut};
+use core::{future::Future, mem::MaybeUninit, ops::DerefMut, ptr};
 
-use crate::MutexedStaticVec;
+use crate::{init_slots_mut, MutexedStaticVec, StaticVecError};
 
 pub trait KeyTrait {
     type Key: Copy + PartialEq;
@@ -18,15 +18,35 @@ pub trait OptionMutexTrait<'a> {
     fn take_item(&self) -> impl Future<Output = Option<Self::Item>>;
 }
 
+pub trait TryLockItemTrait<'a>: OptionMutexTrait<'a> {
+    fn try_lock_item(&'a self) -> Option<Self::Guard>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TryRemoveResult {
+    Removed,
+    NotFound,
+    WouldBlock,
+}
+
 pub trait RemoveWithLocksTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
     fn remove_with_locks<
         KP: Fn(&T::Key) -> bool,
         IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
     >(
-        &self,
+        &mut self,
         key_pred: KP,
         item_pred: IP,
     ) -> impl Future<Output = bool>;
+
+    fn remove_with_locks_returning_key<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) -> impl Future<Output = Option<T::Key>>;
 }
 
 impl<'a, T, const N: usize> RemoveWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
@@ -37,64 +57,366 @@ where
         KP: Fn(&T::Key) -> bool,
         IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
     >(
-        &self,
+        &mut self,
         key_pred: KP,
         item_pred: IP,
     ) -> bool {
+        self.remove_with_locks_returning_key(key_pred, item_pred)
+            .await
+            .is_some()
+    }
+
+    // the last element is moved whole into the hole, so each key stays with its own inner item
+    async fn remove_with_locks_returning_key<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) -> Option<T::Key> {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+
+        for i in 0..*len_locked {
+            let key = {
+                let item = unsafe { (*self.data.get_unchecked(i).get()).assume_init_ref() };
+                let key = item.get_key();
+                if !key_pred(&key) || !item.lock_item().await.as_ref().is_some_and(&item_pred) {
+                    continue;
+                }
+                key
+            };
+
+            // the item lock is released above, so the element can be moved
+            let last_index = *len_locked - 1;
+            let removed = unsafe {
+                let base = self.data.as_mut_ptr();
+                let removed = ptr::read(base.add(i)).into_inner().assume_init();
+                ptr::copy(base.add(last_index), base.add(i), 1);
+                removed
+            };
+            *len_locked = last_index;
+            self.bump_generation();
+            self.bump_versions(i..last_index + 1);
+            drop(removed);
+            return Some(key);
+        }
+
+        None
+    }
+}
+
+pub trait RetainWithLocksTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn retain_with_locks<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) -> impl Future<Output = ()>;
+}
+
+impl<'a, T, const N: usize> RetainWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    // whole elements are moved, so each key stays with its own inner item
+    async fn retain_with_locks<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) {
         let mut len_locked = self.len.lock().await;
-        let len = *len_locked;
+        self.settle(&mut len_locked);
 
-        assert!(len > 0);
+        let mut i = 0;
+        while i < *len_locked {
+            let keep = {
+                let item = unsafe { (*self.data.get_unchecked(i).get()).assume_init_ref() };
+                key_pred(&item.get_key()) && item.lock_item().await.as_ref().is_some_and(&item_pred)
+            };
+            if keep {
+                i += 1;
+                continue;
+            }
+
+            // the item lock is released above, so the element can be moved; the element
+            // swapped in from the back is checked on the next iteration
+            let last_index = *len_locked - 1;
+            let removed = unsafe {
+                let base = self.data.as_mut_ptr();
+                let removed = ptr::read(base.add(i)).into_inner().assume_init();
+                ptr::copy(base.add(last_index), base.add(i), 1);
+                removed
+            };
+            *len_locked = last_index;
+            self.bump_generation();
+            self.bump_versions(i..last_index + 1);
+            drop(removed);
+        }
+    }
+}
+
+pub trait KeyIndexTrait<T: KeyTrait> {
+    fn key_index<KP: Fn(&T::Key) -> bool>(
+        &self,
+        key_pred: KP,
+    ) -> impl Future<Output = Option<usize>>;
+}
+
+impl<T: KeyTrait, const N: usize> KeyIndexTrait<T> for MutexedStaticVec<T, N> {
+    async fn key_index<KP: Fn(&T::Key) -> bool>(&self, key_pred: KP) -> Option<usize> {
+        let len_locked = self.lock_settled().await;
+        self.prefix(*len_locked)
+            .iter()
+            .position(|item| key_pred(&item.get_key()))
+    }
+}
+
+pub trait InsertWithLocksTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn insert_with_locks(&self, item: T) -> impl Future<Output = Result<usize, StaticVecError>>;
+}
+
+impl<'a, T, const N: usize> InsertWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    async fn insert_with_locks(&self, item: T) -> Result<usize, StaticVecError> {
+        let mut len_locked = self.lock_settled().await;
+        let index = *len_locked;
+        if index >= N {
+            return Err(StaticVecError::CapacityExceeded);
+        }
+        unsafe {
+            let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(index).get();
+            el.write(item);
+        }
+        *len_locked = index + 1;
+        self.bump_generation();
+        self.record_len(index + 1);
+        self.bump_versions(index..index + 1);
+        Ok(index)
+    }
+}
+
+pub trait DrainByKeyTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn drain_by_key<KP: Fn(&T::Key) -> bool>(
+        &mut self,
+        key_pred: KP,
+    ) -> impl Future<Output = usize>;
+}
+
+impl<'a, T, const N: usize> DrainByKeyTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    async fn drain_by_key<KP: Fn(&T::Key) -> bool>(&mut self, key_pred: KP) -> usize {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        // a panicking predicate leaks the elements instead of exposing moved-out slots
+        let len = core::mem::replace(&mut *len_locked, 0);
+        self.bump_generation();
+
+        // survivors are moved whole, keeping every key with its own inner item
+        let ptr = self.data.as_mut_ptr();
+        let mut write = 0;
+        for read in 0..len {
+            unsafe {
+                let el = (*ptr.add(read)).get_mut();
+                if key_pred(&el.assume_init_ref().get_key()) {
+                    el.assume_init_drop();
+                    continue;
+                }
+                if read != write {
+                    ptr::copy_nonoverlapping(ptr.add(read), ptr.add(write), 1);
+                }
+            }
+            write += 1;
+        }
+
+        *len_locked = write;
+        self.bump_generation();
+        if write < len {
+            self.bump_versions(0..len);
+        }
+        len - write
+    }
+}
+
+pub trait TryRemoveWithLocksTrait<'a, T: KeyTrait + TryLockItemTrait<'a>> {
+    fn try_remove_with_locks<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) -> TryRemoveResult;
+}
+
+impl<'a, T, const N: usize> TryRemoveWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + TryLockItemTrait<'a> + 'a,
+{
+    // the vec is held exclusively, so only an inner item lock held elsewhere can block; the
+    // last element is moved whole into the hole, keeping each key with its own inner item
+    fn try_remove_with_locks<
+        KP: Fn(&T::Key) -> bool,
+        IP: Fn(&<T as OptionMutexTrait<'_>>::Item) -> bool,
+    >(
+        &mut self,
+        key_pred: KP,
+        item_pred: IP,
+    ) -> TryRemoveResult {
+        let len = self.settled_len();
 
         for i in 0..len {
-            let last_index = len - 1;
             let item = unsafe { (*self.data.get_unchecked(i).get()).assume_init_ref() };
             if !key_pred(&item.get_key()) {
                 continue;
             }
+            let Some(selected) = item.try_lock_item() else {
+                return TryRemoveResult::WouldBlock;
+            };
+            if !selected.as_ref().is_some_and(&item_pred) {
+                continue;
+            }
+            drop(selected);
 
-            if i != last_index {
-                let mut selected = unsafe {
-                    let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(i).get();
-                    el.assume_init_mut().lock_item().await
-                };
+            let last_index = len - 1;
+            let removed = unsafe {
+                let base = self.data.as_mut_ptr();
+                let removed = ptr::read(base.add(i)).into_inner().assume_init();
+                ptr::copy(base.add(last_index), base.add(i), 1);
+                removed
+            };
+            *self.len.get_mut() = last_index;
+            self.bump_generation();
+            self.bump_versions(i..last_index + 1);
+            drop(removed);
+            return TryRemoveResult::Removed;
+        }
 
-                match selected.as_ref() {
-                    Some(item) => {
-                        if !item_pred(item) {
-                            continue;
-                        }
-                    }
-                    None => continue,
-                }
+        TryRemoveResult::NotFound
+    }
+}
 
-                let last = unsafe {
-                    let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(last_index).get();
-                    el.assume_init_mut().take_item().await
-                };
+pub trait PromoteWithLocksTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn promote_with_locks<KP: Fn(&T::Key) -> bool>(
+        &mut self,
+        key_pred: KP,
+    ) -> impl Future<Output = bool>;
+}
 
-                *selected = last;
-            } else {
-                let mut selected = unsafe {
-                    let el: &mut MaybeUninit<T> = &mut *self.data.get_unchecked(i).get();
-                    el.assume_init_mut().lock_item().await
-                };
+impl<'a, T, const N: usize> PromoteWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    async fn promote_with_locks<KP: Fn(&T::Key) -> bool>(&mut self, key_pred: KP) -> bool {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let slots = unsafe { init_slots_mut(&mut self.data[..*len_locked]) };
 
-                match selected.as_ref() {
-                    Some(item) => {
-                        if !item_pred(item) {
-                            continue;
-                        }
-                    }
-                    None => continue,
-                }
+        let Some(index) = slots.iter().position(|item| key_pred(&item.get_key())) else {
+            return false;
+        };
+        slots[..=index].rotate_right(1);
+        self.bump_versions(0..index + 1);
+        true
+    }
+}
 
-                *selected = None;
-            }
+pub trait RebalanceWithLocksTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn rebalance_with_locks(&mut self) -> impl Future<Output = ()>
+    where
+        T::Key: Ord;
+}
 
-            *len_locked = len - 1;
+impl<'a, T, const N: usize> RebalanceWithLocksTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    async fn rebalance_with_locks(&mut self)
+    where
+        T::Key: Ord,
+    {
+        let mut len_locked = self.len.lock().await;
+        self.settle(&mut len_locked);
+        let slots = unsafe { init_slots_mut(&mut self.data[..*len_locked]) };
+
+        // an insertion sort keeps equal keys in order without allocating; whole elements are
+        // moved and keys come from the outer elements, so no inner item is ever locked
+        for i in 1..slots.len() {
+            let key = slots[i].get_key();
+            let dst = slots[..i].partition_point(|item| item.get_key() <= key);
+            slots[dst..=i].rotate_right(1);
         }
+        self.bump_versions(0..*len_locked);
+    }
+}
+
+pub trait GetByKeyClonedTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn get_by_key_cloned<KP: Fn(&T::Key) -> bool>(
+        &self,
+        key_pred: KP,
+    ) -> impl Future<Output = Option<<T as OptionMutexTrait<'a>>::Item>>
+    where
+        <T as OptionMutexTrait<'a>>::Item: Clone;
+}
+
+impl<'a, T, const N: usize> GetByKeyClonedTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    async fn get_by_key_cloned<KP: Fn(&T::Key) -> bool>(
+        &self,
+        key_pred: KP,
+    ) -> Option<<T as OptionMutexTrait<'a>>::Item>
+    where
+        <T as OptionMutexTrait<'a>>::Item: Clone,
+    {
+        let len_locked = self.lock_settled().await;
+        let index = self
+            .prefix(*len_locked)
+            .iter()
+            .position(|item| key_pred(&item.get_key()))?;
 
-        false
+        // the inner lock is released before returning, only the clone escapes
+        let item = unsafe { (*self.data.get_unchecked(index).get()).assume_init_ref() };
+        let selected = item.lock_item().await;
+        selected.clone()
+    }
+}
+
+#[cfg(feature = "futures")]
+pub trait ItemsStreamTrait<'a, T: KeyTrait + OptionMutexTrait<'a>> {
+    fn items_stream(&'a self) -> impl futures_core::Stream<Item = (T::Key, T::Guard)> + 'a;
+}
+
+#[cfg(feature = "futures")]
+impl<'a, T, const N: usize> ItemsStreamTrait<'a, T> for MutexedStaticVec<T, N>
+where
+    T: KeyTrait + OptionMutexTrait<'a> + 'a,
+{
+    // the stream only holds the length lock, each item guard is owned by the caller once yielded
+    fn items_stream(&'a self) -> impl futures_core::Stream<Item = (T::Key, T::Guard)> + 'a {
+        futures_util::stream::unfold(
+            (None::<tokio::sync::MutexGuard<'a, usize>>, 0),
+            move |(len_locked, index)| async move {
+                let len_locked = match len_locked {
+                    Some(len_locked) => len_locked,
+                    None => self.lock_settled().await,
+                };
+                let item = self.prefix(*len_locked).get(index)?;
+                let key = item.get_key();
+                let selected = item.lock_item().await;
+                Some(((key, selected), (Some(len_locked), index + 1)))
+            },
+        )
     }
 }
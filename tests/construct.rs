@@ -0,0 +1,70 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+#[tokio::test]
+async fn new_const_len_fills_with_defaults() {
+    let v = MutexedStaticVec::<u32, 4>::new_const_len::<2>();
+    assert_eq!(v.as_slice().await, &[0, 0]);
+    let v = MutexedStaticVec::<String, 2>::new_const_len::<2>();
+    assert_eq!(v.as_slice().await, ["", ""]);
+}
+
+#[tokio::test]
+async fn repeat_clones_up_to_capacity() {
+    let v = MutexedStaticVec::<u32, 4>::repeat(7, 3).await.unwrap();
+    assert_eq!(v.as_slice().await, &[7, 7, 7]);
+    assert!(
+        MutexedStaticVec::<u32, 4>::repeat(7, 0)
+            .await
+            .unwrap()
+            .is_empty()
+            .await
+    );
+    assert_eq!(
+        MutexedStaticVec::<u32, 4>::repeat(7, 5).await.err(),
+        Some(mutexedstaticvec::StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn try_from_chunks_flattens_records() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_chunks([[1, 2], [3, 4]])
+        .await
+        .unwrap();
+    assert_eq!(v.as_slice().await, &[1, 2, 3, 4]);
+    assert_eq!(
+        MutexedStaticVec::<u32, 4>::try_from_chunks([[1, 2, 3], [4, 5, 6]])
+            .await
+            .err(),
+        Some(mutexedstaticvec::StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn try_from_results_stops_at_the_first_error() {
+    use mutexedstaticvec::TryFromResultsError;
+
+    let v = MutexedStaticVec::<u32, 4>::try_from_results([Ok::<_, &str>(1), Ok(2)])
+        .await
+        .unwrap();
+    assert_eq!(v.as_slice().await, &[1, 2]);
+    assert_eq!(
+        MutexedStaticVec::<u32, 4>::try_from_results([Ok(1), Err("bad"), Ok(3)])
+            .await
+            .err(),
+        Some(TryFromResultsError::Item("bad"))
+    );
+    assert_eq!(
+        MutexedStaticVec::<u32, 2>::try_from_results((0..3).map(Ok::<_, &str>))
+            .await
+            .err(),
+        Some(TryFromResultsError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn from_array_truncating_keeps_the_first_n() {
+    let v = MutexedStaticVec::<u32, 2>::from_array_truncating([1, 2, 3]).await;
+    assert_eq!(v.as_slice().await, &[1, 2]);
+    let v = MutexedStaticVec::<u32, 4>::from_array_truncating([1, 2, 3]).await;
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+}
@@ -0,0 +1,67 @@
+// from_array carries a `[(); N - A]:` bound, so callers need generic_const_exprs as well
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use mutexedstaticvec::copy_vec::MutexedCopyVec;
+use mutexedstaticvec::StaticVecError;
+
+#[tokio::test]
+async fn push_remove() {
+    let mut v = MutexedCopyVec::<u64, 3>::new();
+    assert!(v.is_empty().await);
+    for i in 1..=3 {
+        v.push(i * 10).await.unwrap();
+    }
+    assert_eq!(v.push(40).await, Err(StaticVecError::CapacityExceeded));
+    assert_eq!(v.as_slice().await, &[10, 20, 30]);
+
+    assert_eq!(v.remove(0).await, 10);
+    v.iter_mut().await.for_each(|x| *x += 1);
+    assert_eq!(v.iter().await.copied().collect::<Vec<_>>(), [21, 31]);
+    v.try_extend_from_slice(&[5]).await.unwrap();
+    assert_eq!(v.as_slice().await, &[21, 31, 5]);
+}
+
+#[tokio::test]
+async fn swap_remove_moves_the_last_element() {
+    let mut v = MutexedCopyVec::from([1u8, 2, 3, 4]);
+    assert_eq!(v.swap_remove(1).await, 2);
+    assert_eq!(v.as_slice().await, &[1, 4, 3]);
+    assert_eq!(v.swap_remove(2).await, 3);
+    assert_eq!(v.as_slice().await, &[1, 4]);
+}
+
+#[tokio::test]
+async fn constructors() {
+    let v = MutexedCopyVec::<u64, 4>::with_len(2).unwrap();
+    assert_eq!(v.as_slice().await, &[0, 0]);
+    assert!(MutexedCopyVec::<u64, 4>::with_len(5).is_err());
+
+    let v = MutexedCopyVec::from([1u8, 2, 3]);
+    assert_eq!(v.len().await, 3);
+
+    let mut v = MutexedCopyVec::<u8, 4>::from_array([1, 2]).await;
+    assert_eq!(v.as_slice().await, &[1, 2]);
+    v.push(3).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+
+    let mut v = MutexedCopyVec::<u8, 2>::default();
+    assert_eq!(
+        v.try_extend_from_iter([1, 2, 3].into_iter()).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+    assert_eq!(v.as_slice().await, &[1, 2]);
+}
+
+#[tokio::test]
+async fn extend_from_iter_ref_copies() {
+    let mut v = MutexedCopyVec::<u32, 3>::new();
+    let src = [7, 8];
+    v.try_extend_from_iter_ref(src.iter()).await.unwrap();
+    assert_eq!(v.as_slice().await, &[7, 8]);
+    assert_eq!(
+        v.try_extend_from_iter_ref(src.iter()).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+    assert_eq!(v.as_slice().await, &[7, 8, 7]);
+}
@@ -0,0 +1,424 @@
+#[cfg(feature = "versioned")]
+#[tokio::test]
+async fn versioned_read_detects_a_write() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    let (value, version) = v.read_versioned(1).await.unwrap();
+    assert_eq!(value, 2);
+    assert_eq!(v.version(1), version);
+
+    v.update(1, |x| *x = 5).await.unwrap();
+    assert_ne!(v.version(1), version);
+    assert_eq!(v.read_versioned(1).await.unwrap().0, 5);
+}
+
+#[cfg(feature = "versioned")]
+#[tokio::test]
+async fn versioned_read_detects_a_remove_shifting_the_slot() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let (_, version) = v.read_versioned(0).await.unwrap();
+
+    assert_eq!(v.remove(0).await, 1);
+    let (value, new_version) = v.read_versioned(0).await.unwrap();
+    assert_eq!(value, 2);
+    assert_ne!(new_version, version);
+}
+
+#[cfg(feature = "versioned")]
+#[tokio::test]
+async fn versioned_read_detects_apply_all() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    let versions = [v.version(0), v.version(1)];
+
+    v.apply_all(|x| *x *= 10).await;
+    assert_ne!(v.version(0), versions[0]);
+    assert_ne!(v.version(1), versions[1]);
+}
+
+#[cfg(feature = "high-water")]
+#[tokio::test]
+async fn high_water_mark_survives_removals() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 8>::default();
+    for i in 0..5 {
+        v.push(i).await.unwrap();
+    }
+    for _ in 0..3 {
+        v.remove(0).await;
+    }
+    assert_eq!(v.len().await, 2);
+    assert_eq!(v.high_water_mark().await, 5);
+
+    let (odd, even) = MutexedStaticVec::<u32, 8>::try_from_iter([1, 2, 3, 4])
+        .await
+        .unwrap()
+        .partition(|x| x % 2 == 1)
+        .await;
+    assert_eq!(odd.high_water_mark().await, 2);
+    assert_eq!(even.high_water_mark().await, 2);
+}
+
+#[cfg(feature = "debug-checks")]
+#[tokio::test]
+#[should_panic(expected = "length changed while a borrowed view was in use")]
+async fn generation_catches_a_racing_push() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    let reader = async {
+        let generation = v.generation();
+        let items = v.as_slice().await;
+        tokio::task::yield_now().await;
+        assert_eq!(items, &[1, 2]);
+        v.assert_generation(generation);
+    };
+    let writer = async {
+        v.push(3).await.unwrap();
+    };
+    tokio::join!(reader, writer);
+}
+
+#[cfg(feature = "lazy-remove")]
+#[tokio::test]
+async fn lazy_remove_tombstones_until_compact() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v =
+        MutexedStaticVec::<String, 4>::try_from_iter(["a", "b", "c", "d"].map(String::from))
+            .await
+            .unwrap();
+    assert_eq!(v.remove(1).await, "b");
+    // indices count live elements, so index 1 is now "c"
+    assert_eq!(v.remove(1).await, "c");
+    v.compact().await;
+    assert_eq!(v.as_slice().await, ["a", "d"]);
+
+    assert_eq!(v.remove(0).await, "a");
+    v.push("e".into()).await.unwrap();
+    assert_eq!(v.as_slice().await, ["d", "e"]);
+    // the vec has no Drop, so move the strings out to free them
+    drop(v.take_all_into::<Vec<_>>().await);
+}
+
+#[cfg(feature = "lazy-remove")]
+#[tokio::test]
+async fn lazy_remove_guard() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v =
+        MutexedStaticVec::<String, 4>::try_from_iter(["a", "b", "c", "d"].map(String::from))
+            .await
+            .unwrap();
+    {
+        let mut lazy = v.lazy_remove();
+        assert_eq!(lazy.remove(0), "a");
+        assert_eq!(lazy.remove(2), "c");
+        assert!(lazy.is_removed(0));
+        assert_eq!(lazy.get(0), None);
+        assert_eq!(lazy.len(), 2);
+        assert_eq!(lazy.iter().collect::<Vec<_>>(), ["b", "d"]);
+
+        lazy.compact();
+        assert_eq!(lazy.get(0).map(String::as_str), Some("b"));
+        assert_eq!(lazy.remove(1), "d");
+    }
+    assert_eq!(v.as_slice().await, ["b"]);
+
+    // a leaked guard leaves the vec empty rather than exposing moved-out slots
+    let mut lazy = v.lazy_remove();
+    drop(lazy.remove(0));
+    std::mem::forget(lazy);
+    assert!(v.as_slice().await.is_empty());
+}
+
+#[cfg(feature = "lazy-remove")]
+#[tokio::test]
+async fn tombstones_are_skipped_and_not_counted() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 8>::try_from_iter(0..6)
+        .await
+        .unwrap();
+    v.remove(4).await;
+    v.remove(1).await;
+    v.remove(0).await;
+    assert_eq!(v.len().await, 3);
+    assert_eq!(v.stats().await, (3, 5, 8));
+    assert!(v.is_valid_index(2).await && !v.is_valid_index(3).await);
+
+    assert_eq!(v.iter().await.copied().collect::<Vec<_>>(), [2, 3, 5]);
+    assert_eq!(v.as_slice().await, &[2, 3, 5]);
+    while !v.is_empty().await {
+        v.remove(0).await;
+    }
+    assert_eq!(v.len().await, 0);
+}
+
+#[cfg(feature = "rayon")]
+// crossbeam-epoch, under rayon's work stealing, trips Stacked Borrows on its own; the test
+// passes under -Zmiri-tree-borrows
+#[cfg_attr(miri, ignore)]
+#[tokio::test]
+async fn par_for_each_visits_every_element() {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<u64, 64>::try_from_iter(1..=64)
+        .await
+        .unwrap();
+    let sum = AtomicU64::new(0);
+    v.par_for_each(|&x| {
+        sum.fetch_add(x, Ordering::Relaxed);
+    })
+    .await;
+    assert_eq!(sum.into_inner(), 64 * 65 / 2);
+}
+
+#[cfg(feature = "bytemuck")]
+#[tokio::test]
+async fn as_bytes_covers_the_prefix_only() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<u16, 4>::try_from_iter([0x0102, 0x0304])
+        .await
+        .unwrap();
+    let expected: Vec<u8> = [0x0102u16, 0x0304]
+        .iter()
+        .flat_map(|x| x.to_ne_bytes())
+        .collect();
+    assert_eq!(v.as_bytes().await, &expected[..]);
+    assert!(MutexedStaticVec::<u16, 4>::default()
+        .as_bytes()
+        .await
+        .is_empty());
+}
+
+#[cfg(feature = "bytemuck")]
+#[tokio::test]
+async fn from_bytes_round_trips_and_rejects_bad_input() {
+    use mutexedstaticvec::{MutexedStaticVec, StaticVecError};
+
+    let bytes: Vec<u8> = [1u32, 2, 3].iter().flat_map(|x| x.to_ne_bytes()).collect();
+    let v = MutexedStaticVec::<u32, 4>::from_bytes(&bytes)
+        .await
+        .unwrap();
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+
+    // an odd offset exercises the unaligned read
+    let mut shifted = vec![0u8];
+    shifted.extend_from_slice(&bytes);
+    let v = MutexedStaticVec::<u32, 4>::from_bytes(&shifted[1..])
+        .await
+        .unwrap();
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+
+    assert_eq!(
+        MutexedStaticVec::<u32, 4>::from_bytes(&bytes[..5])
+            .await
+            .err(),
+        Some(StaticVecError::InvalidByteLength { len: 5 })
+    );
+    assert_eq!(
+        MutexedStaticVec::<u32, 2>::from_bytes(&bytes).await.err(),
+        Some(StaticVecError::CapacityExceeded)
+    );
+}
+
+#[cfg(feature = "arrayvec")]
+#[tokio::test]
+async fn arrayvec_round_trip() {
+    use arrayvec::ArrayVec;
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut a = ArrayVec::<u32, 4>::new();
+    a.push(1);
+    a.push(2);
+    let v = MutexedStaticVec::from(a);
+    assert_eq!(v.as_slice().await, &[1, 2]);
+
+    v.push(3).await.unwrap();
+    let a = ArrayVec::from(v);
+    assert_eq!(a.as_slice(), &[1, 2, 3]);
+}
+
+#[cfg(feature = "heapless")]
+#[tokio::test]
+async fn heapless_round_trip() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut h = heapless::Vec::<u32, 4>::new();
+    h.push(1).unwrap();
+    h.push(2).unwrap();
+    let v = MutexedStaticVec::from(h);
+    assert_eq!(v.as_slice().await, &[1, 2]);
+    assert_eq!(v.to_heapless().await.as_slice(), &[1, 2]);
+}
+
+#[cfg(feature = "bytemuck")]
+#[tokio::test]
+async fn zeroize_clears_the_spare_slots() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    v.pop_front().await;
+    v.zeroize_spare().await;
+    assert_eq!(v.as_slice().await, &[2, 3]);
+    {
+        let _len = unsafe { v.lock_len().await };
+        let spare: Vec<u32> = (2..4).map(|i| unsafe { *v.as_ptr().add(i) }).collect();
+        assert_eq!(spare, [0, 0]);
+    }
+
+    v.zeroize().await;
+    assert!(v.is_empty().await);
+    let _len = unsafe { v.lock_len().await };
+    let all: Vec<u32> = (0..4).map(|i| unsafe { *v.as_ptr().add(i) }).collect();
+    assert_eq!(all, [0; 4]);
+}
+
+#[cfg(feature = "bytemuck")]
+#[tokio::test]
+async fn zeroize_scrubs_padding_bytes() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    // three padding bytes follow `a`
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Padded {
+        a: u8,
+        b: u32,
+    }
+    unsafe impl bytemuck::Zeroable for Padded {}
+
+    const SIZE: usize = core::mem::size_of::<[Padded; 2]>();
+    let mut v = MutexedStaticVec::<Padded, 2>::default();
+    {
+        let _len = unsafe { v.lock_len().await };
+        unsafe { (v.as_ptr() as *mut u8).write_bytes(0xff, SIZE) };
+    }
+    v.push(Padded { a: 1, b: 2 }).await.unwrap();
+
+    v.zeroize_spare().await;
+    {
+        let _len = unsafe { v.lock_len().await };
+        let spare =
+            unsafe { core::slice::from_raw_parts(v.as_ptr().add(1) as *const u8, SIZE / 2) };
+        assert!(spare.iter().all(|&b| b == 0));
+    }
+
+    v.zeroize().await;
+    let _len = unsafe { v.lock_len().await };
+    let all = unsafe { core::slice::from_raw_parts(v.as_ptr() as *const u8, SIZE) };
+    assert!(all.iter().all(|&b| b == 0));
+}
+
+#[cfg(feature = "debug-checks")]
+#[tokio::test]
+async fn assert_invariants_accepts_a_consistent_vec() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 3>::default();
+    v.assert_invariants().await;
+    v.push_many([1, 2, 3]).await.unwrap();
+    v.assert_invariants().await;
+    assert_eq!(v.remove(0).await, 1);
+    v.assert_invariants().await;
+    v.push(4).await.unwrap();
+    v.assert_invariants().await;
+    assert_eq!(v.as_slice().await, &[2, 3, 4]);
+}
+
+#[cfg(feature = "crc")]
+#[tokio::test]
+async fn crc32_matches_the_check_value() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<u8, 16>::try_from_iter(*b"123456789")
+        .await
+        .unwrap();
+    assert_eq!(v.crc32().await, 0xCBF4_3926);
+    assert_eq!(MutexedStaticVec::<u8, 16>::default().crc32().await, 0);
+}
+
+#[cfg(feature = "debug-checks")]
+#[tokio::test]
+#[should_panic(expected = "never written")]
+async fn set_len_rejects_unwritten_slots() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u32, 4>::default();
+    v.spare_capacity_mut().write(0, 1);
+    unsafe { v.set_len(2) };
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn from_stream_collects_until_capacity() {
+    use mutexedstaticvec::{MutexedStaticVec, StaticVecError};
+
+    let v = MutexedStaticVec::<u32, 4>::from_stream(futures::stream::iter(0..3))
+        .await
+        .unwrap();
+    assert_eq!(v.as_slice().await, &[0, 1, 2]);
+    assert_eq!(
+        MutexedStaticVec::<u32, 4>::from_stream(futures::stream::iter(0..5))
+            .await
+            .err(),
+        Some(StaticVecError::CapacityExceeded)
+    );
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn into_stream_yields_owned_elements() {
+    use futures::StreamExt;
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<String, 4>::try_from_iter(["a".to_string(), "b".to_string()])
+        .await
+        .unwrap();
+    let items: Vec<String> = v.into_stream().collect().await;
+    assert_eq!(items, ["a", "b"]);
+}
+
+#[cfg(feature = "std")]
+#[tokio::test]
+async fn fill_from_read_appends_up_to_capacity() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let mut v = MutexedStaticVec::<u8, 4>::try_from_iter([1]).await.unwrap();
+    let mut reader: &[u8] = b"abcdef";
+    assert_eq!(v.fill_from_read(&mut reader).await.unwrap(), 3);
+    assert_eq!(v.as_slice().await, b"\x01abc");
+    assert_eq!(reader, b"def");
+    assert_eq!(v.fill_from_read(&mut reader).await.unwrap(), 0);
+}
+
+#[cfg(feature = "std")]
+#[tokio::test]
+async fn write_to_writes_the_prefix() {
+    use mutexedstaticvec::MutexedStaticVec;
+
+    let v = MutexedStaticVec::<u8, 8>::try_from_iter(*b"hi")
+        .await
+        .unwrap();
+    let mut out = Vec::new();
+    assert_eq!(v.write_to(&mut out).await.unwrap(), 2);
+    assert_eq!(out, b"hi");
+}
@@ -0,0 +1,14 @@
+// from_array and from_option_array carry a `[(); N - A]:` bound, so callers need
+// generic_const_exprs as well
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use mutexedstaticvec::MutexedStaticVec;
+
+#[tokio::test]
+async fn from_option_array_packs_the_some_values() {
+    let v = MutexedStaticVec::<u32, 4>::from_option_array([Some(1), None, Some(3)]).await;
+    assert_eq!(v.as_slice().await, &[1, 3]);
+    let v = MutexedStaticVec::<u32, 4>::from_option_array([None::<u32>; 4]).await;
+    assert!(v.is_empty().await);
+}
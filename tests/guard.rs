@@ -0,0 +1,18 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+#[tokio::test]
+async fn lock_mut_remove_then_push() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    {
+        let mut guard = v.lock_mut().await;
+        let removed = guard.remove(0);
+        guard.push(removed * 10).unwrap();
+        assert_eq!(guard.len(), 3);
+        assert_eq!(guard.get(2), Some(&10));
+        *guard.get_mut(0).unwrap() += 1;
+        assert_eq!(guard.pop(), Some(10));
+    }
+    assert_eq!(v.as_slice().await, &[3, 3]);
+}
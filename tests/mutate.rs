@@ -0,0 +1,479 @@
+use mutexedstaticvec::{MutexedStaticVec, StaticVecError};
+
+#[tokio::test]
+async fn update_in_place() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    v.update(1, |x| *x += 1).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1, 3, 3]);
+    assert_eq!(
+        v.update(3, |x| *x += 1).await,
+        Err(StaticVecError::OutOfBounds { index: 3, len: 3 })
+    );
+}
+
+#[tokio::test]
+async fn increment_from_several_tasks() {
+    let v = tokio::sync::Mutex::new(
+        MutexedStaticVec::<u64, 2>::try_from_iter([0])
+            .await
+            .unwrap(),
+    );
+    let task = || async {
+        for _ in 0..100 {
+            v.lock().await.increment(0, 1).await.unwrap();
+        }
+    };
+    tokio::join!(task(), task(), task(), task());
+
+    let mut v = v.into_inner();
+    assert_eq!(v.as_slice().await, &[400]);
+    assert_eq!(v.increment(0, 5).await, Ok(405));
+    assert_eq!(
+        v.increment(1, 1).await,
+        Err(StaticVecError::OutOfBounds { index: 1, len: 1 })
+    );
+}
+
+#[tokio::test]
+async fn replace_returns_old_value() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([10, 20, 30])
+        .await
+        .unwrap();
+    assert_eq!(v.replace(1, 99).await, Ok(20));
+    assert_eq!(v.as_slice().await, &[10, 99, 30]);
+    assert_eq!(
+        v.replace(3, 0).await,
+        Err(StaticVecError::OutOfBounds { index: 3, len: 3 })
+    );
+}
+
+#[tokio::test]
+async fn swap_remove_moves_the_last_element_into_the_hole() {
+    let mut v =
+        MutexedStaticVec::<String, 4>::try_from_iter(["a", "b", "c", "d"].map(String::from))
+            .await
+            .unwrap();
+    assert_eq!(v.swap_remove(1).await, "b");
+    assert_eq!(v.as_slice().await, ["a", "d", "c"]);
+    assert_eq!(v.swap_remove(2).await, "c");
+    assert_eq!(v.as_slice().await, ["a", "d"]);
+    // the vec has no Drop, so move the strings out to free them
+    drop(v.take_all_into::<Vec<_>>().await);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn swap_remove_out_of_range_panics() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    v.swap_remove(1).await;
+}
+
+#[tokio::test]
+async fn push_many_stops_at_capacity() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    assert_eq!(v.push_many([2, 3]).await, Ok(2));
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+    assert_eq!(
+        v.push_many([4, 5, 6]).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+    assert_eq!(v.as_slice().await, &[1, 2, 3, 4]);
+    assert_eq!(v.push_many([]).await, Ok(0));
+}
+
+#[tokio::test]
+async fn insert_sorted_keeps_order() {
+    let mut v = MutexedStaticVec::<u32, 4>::default();
+    assert_eq!(v.insert_sorted(5).await, Ok(0));
+    assert_eq!(v.insert_sorted(1).await, Ok(0));
+    assert_eq!(v.insert_sorted(3).await, Ok(1));
+    // equal elements go after the existing ones
+    assert_eq!(v.insert_sorted(3).await, Ok(2));
+    assert_eq!(v.as_slice().await, &[1, 3, 3, 5]);
+    assert_eq!(
+        v.insert_sorted(2).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn remove_indices_in_any_order() {
+    let mut v = MutexedStaticVec::<u32, 8>::try_from_iter(0..6)
+        .await
+        .unwrap();
+    v.remove_indices(&[4, 0, 2]).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1, 3, 5]);
+
+    assert_eq!(
+        v.remove_indices(&[1, 3]).await,
+        Err(StaticVecError::OutOfBounds { index: 3, len: 3 })
+    );
+    assert_eq!(
+        v.remove_indices(&[1, 1]).await,
+        Err(StaticVecError::DuplicateIndex { index: 1 })
+    );
+    // a rejected call leaves the vec untouched
+    assert_eq!(v.as_slice().await, &[1, 3, 5]);
+}
+
+#[tokio::test]
+async fn take_raw_moves_the_storage_out() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([7, 8])
+        .await
+        .unwrap();
+    let (data, len) = v.take_raw().await;
+    assert_eq!(len, 2);
+    let items: Vec<u32> = data[..len]
+        .iter()
+        .map(|x| unsafe { x.assume_init() })
+        .collect();
+    assert_eq!(items, [7, 8]);
+    assert!(v.is_empty().await);
+
+    v.push(1).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1]);
+}
+
+#[tokio::test]
+async fn into_array_only_when_full() {
+    let v = MutexedStaticVec::<u32, 3>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    let v = v.into_array().await.unwrap_err();
+    assert_eq!(v.as_slice().await, &[1, 2]);
+
+    v.push(3).await.unwrap();
+    assert_eq!(v.into_array().await.ok(), Some([1, 2, 3]));
+}
+
+#[tokio::test]
+async fn replace_all_swaps_the_contents() {
+    let mut v = MutexedStaticVec::<u32, 3>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    v.replace_all([7, 8, 9]).await.unwrap();
+    assert_eq!(v.as_slice().await, &[7, 8, 9]);
+
+    // too many items leaves the vec empty instead of half-replaced
+    assert_eq!(
+        v.replace_all(0..4).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+    assert!(v.is_empty().await);
+}
+
+#[tokio::test]
+async fn push_cloned_leaves_the_original() {
+    let mut v = MutexedStaticVec::<String, 1>::default();
+    let s = String::from("a");
+    assert_eq!(v.push_cloned(&s).await.unwrap(), "a");
+    assert_eq!(
+        v.push_cloned(&s).await.err(),
+        Some(StaticVecError::CapacityExceeded)
+    );
+    assert_eq!(s, "a");
+    // the vec has no Drop, so move the strings out to free them
+    drop(v.take_all_into::<Vec<_>>().await);
+}
+
+#[tokio::test]
+async fn saturating_push_drops_when_full() {
+    let v = MutexedStaticVec::<u32, 2>::default();
+    assert!(v.saturating_push(1).await);
+    assert!(v.saturating_push(2).await);
+    assert!(!v.saturating_push(3).await);
+    assert_eq!(v.as_slice().await, &[1, 2]);
+}
+
+#[tokio::test]
+async fn retain_reports_the_removed_count() {
+    let mut v = MutexedStaticVec::<u32, 8>::try_from_iter(0..6)
+        .await
+        .unwrap();
+    assert_eq!(v.retain(|x| x % 3 != 0).await, 2);
+    assert_eq!(v.as_slice().await, &[1, 2, 4, 5]);
+    assert_eq!(v.retain(|_| true).await, 0);
+    assert_eq!(
+        v.retain_mut(|x| {
+            *x += 1;
+            *x < 5
+        })
+        .await,
+        2
+    );
+    assert_eq!(v.as_slice().await, &[2, 3]);
+}
+
+#[tokio::test]
+async fn move_element_shifts_the_range_between() {
+    let mut v = MutexedStaticVec::<u32, 8>::try_from_iter(0..5)
+        .await
+        .unwrap();
+    v.move_element(0, 3).await;
+    assert_eq!(v.as_slice().await, &[1, 2, 3, 0, 4]);
+    v.move_element(4, 1).await;
+    assert_eq!(v.as_slice().await, &[1, 4, 2, 3, 0]);
+    v.move_element(2, 2).await;
+    assert_eq!(v.as_slice().await, &[1, 4, 2, 3, 0]);
+}
+
+#[tokio::test]
+async fn swap_contents_exchanges_elements_and_lengths() {
+    let mut a = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let mut b = MutexedStaticVec::<u32, 4>::try_from_iter([9])
+        .await
+        .unwrap();
+    a.swap_contents(&mut b).await;
+    assert_eq!(a.as_slice().await, &[9]);
+    assert_eq!(b.as_slice().await, &[1, 2, 3]);
+}
+
+#[tokio::test]
+async fn peek_mut_keeps_or_reverts() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    {
+        let mut peek = v.peek_mut(0).await.unwrap();
+        *peek = 10;
+    }
+    {
+        let mut peek = v.peek_mut(1).await.unwrap();
+        *peek = 20;
+        assert_eq!(*peek, 20);
+        peek.cancel();
+    }
+    assert_eq!(v.as_slice().await, &[10, 2]);
+    assert!(v.peek_mut(2).await.is_none());
+}
+
+#[tokio::test]
+async fn drain_into_moves_what_fits() {
+    let mut src = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3, 4])
+        .await
+        .unwrap();
+    let mut dst = MutexedStaticVec::<u32, 3>::try_from_iter([9])
+        .await
+        .unwrap();
+    assert_eq!(src.drain_into(&mut dst, 3).await, Ok(2));
+    assert_eq!(src.as_slice().await, &[3, 4]);
+    assert_eq!(dst.as_slice().await, &[9, 1, 2]);
+    assert_eq!(src.drain_into(&mut dst, 1).await, Ok(0));
+}
+
+#[tokio::test]
+async fn apply_all_mutates_every_element() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    v.apply_all(|x| *x *= 2).await;
+    assert_eq!(v.as_slice().await, &[2, 4, 6]);
+}
+
+#[tokio::test]
+async fn extend_keeps_items_written_before_a_panic() {
+    use futures::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    let mut v = MutexedStaticVec::<u32, 8>::try_from_iter([1])
+        .await
+        .unwrap();
+    let items = [2, 3, 0]
+        .into_iter()
+        .inspect(|&x| assert_ne!(x, 0, "iterator failed"));
+    let res = AssertUnwindSafe(v.try_extend_from_iter(items))
+        .catch_unwind()
+        .await;
+    assert!(res.is_err());
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+
+    assert_eq!(
+        MutexedStaticVec::<u32, 2>::try_from_iter(0..3).await.err(),
+        Some(StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn get_disjoint_mut_reports_why_it_failed() {
+    use mutexedstaticvec::GetDisjointError;
+
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let [a, b] = v.get_disjoint_mut([0, 2]).await.unwrap();
+    core::mem::swap(a, b);
+    assert_eq!(v.as_slice().await, &[3, 2, 1]);
+
+    assert_eq!(
+        v.get_disjoint_mut([0, 3]).await.err(),
+        Some(GetDisjointError::IndexOutOfBounds)
+    );
+    assert_eq!(
+        v.get_disjoint_mut([1, 1]).await.err(),
+        Some(GetDisjointError::OverlappingIndices)
+    );
+}
+
+#[tokio::test]
+async fn partition_keeps_relative_order() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter(0..6)
+        .await
+        .unwrap();
+    let (even, odd) = v.partition(|x| x % 2 == 0).await;
+    assert_eq!(even.as_slice().await, &[0, 2, 4]);
+    assert_eq!(odd.as_slice().await, &[1, 3, 5]);
+}
+
+#[tokio::test]
+async fn push_if_absent_hands_the_item_back() {
+    use mutexedstaticvec::PushIfAbsentError;
+
+    let v = MutexedStaticVec::<u32, 2>::default();
+    assert_eq!(v.push_if_absent(1).await, Ok(&1));
+    assert_eq!(
+        v.push_if_absent(1).await,
+        Err(PushIfAbsentError::AlreadyPresent(1))
+    );
+    assert_eq!(v.push_if_absent(2).await, Ok(&2));
+    // a duplicate is reported even when the vec is also full
+    assert_eq!(
+        v.push_if_absent(2).await,
+        Err(PushIfAbsentError::AlreadyPresent(2))
+    );
+    assert_eq!(
+        v.push_if_absent(3).await,
+        Err(PushIfAbsentError::CapacityExceeded(3))
+    );
+}
+
+#[tokio::test]
+async fn lock_len_and_as_ptr_write_a_slot() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    {
+        let mut len = unsafe { v.lock_len().await };
+        unsafe { v.as_ptr().add(*len).write(2) };
+        *len += 1;
+    }
+    assert_eq!(v.as_slice().await, &[1, 2]);
+}
+
+#[tokio::test]
+async fn lock_len_blocks_other_operations() {
+    use futures::FutureExt;
+
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    let len = unsafe { v.lock_len().await };
+    assert!(v.push(2).now_or_never().is_none());
+    assert!(v.len().now_or_never().is_none());
+    assert!(v.as_slice().now_or_never().is_none());
+    drop(len);
+
+    assert_eq!(v.push(2).now_or_never().unwrap(), Ok(&2));
+    assert_eq!(v.len().now_or_never(), Some(2));
+}
+
+#[tokio::test]
+async fn take_all_into_empties_the_vec() {
+    let mut v = MutexedStaticVec::<String, 4>::try_from_iter(["a".to_string(), "b".to_string()])
+        .await
+        .unwrap();
+    let taken: Vec<String> = v.take_all_into().await;
+    assert_eq!(taken, ["a", "b"]);
+    assert!(v.is_empty().await);
+
+    v.push("c".to_string()).await.unwrap();
+    let taken: std::collections::VecDeque<String> = v.take_all_into().await;
+    assert_eq!(taken, ["c"]);
+}
+
+#[tokio::test]
+async fn spare_capacity_then_set_len() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    let mut spare = v.spare_capacity_mut();
+    assert_eq!(spare.len(), 3);
+    spare.write(0, 2);
+    spare.write(1, 3);
+    unsafe { v.set_len(3) };
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+    v.spare_capacity_mut().write(0, 4);
+    unsafe { v.set_len(4) };
+    assert!(v.spare_capacity_mut().is_empty());
+}
+
+#[tokio::test]
+async fn split_into_frames_keeps_a_short_tail() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter(0..5)
+        .await
+        .unwrap();
+    let mut frames = Vec::new();
+    for frame in v.split_into_frames::<2>().await {
+        frames.push(frame.as_slice().await.to_vec());
+    }
+    assert_eq!(frames, [vec![0, 1], vec![2, 3], vec![4]]);
+
+    let empty = MutexedStaticVec::<u32, 8>::default();
+    assert_eq!(empty.split_into_frames::<2>().await.count(), 0);
+}
+
+#[tokio::test]
+async fn try_apply_all_stops_at_the_first_error() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let res = v
+        .try_apply_all(|x| {
+            if *x == 2 {
+                return Err("two");
+            }
+            *x *= 10;
+            Ok(())
+        })
+        .await;
+    assert_eq!(res, Err("two"));
+    assert_eq!(v.as_slice().await, &[10, 2, 3]);
+}
+
+#[tokio::test]
+async fn remove_if_checks_the_predicate_first() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.remove_if(1, |&x| x > 5).await, None);
+    assert_eq!(v.remove_if(1, |&x| x == 2).await, Some(2));
+    assert_eq!(v.as_slice().await, &[1, 3]);
+}
+
+#[tokio::test]
+async fn resize_with_grows_and_shrinks() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1])
+        .await
+        .unwrap();
+    let mut next = 10;
+    v.resize_with(3, || {
+        next += 1;
+        next
+    })
+    .await
+    .unwrap();
+    assert_eq!(v.as_slice().await, &[1, 11, 12]);
+    v.resize_with(1, || unreachable!()).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1]);
+    assert_eq!(
+        v.resize_with(5, || 0).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+}
@@ -0,0 +1,389 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+#[tokio::test]
+async fn split_first_and_last() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.split_first().await, Some((&1, &[2, 3][..])));
+    assert_eq!(v.split_last().await, Some((&3, &[1, 2][..])));
+
+    let empty = MutexedStaticVec::<u32, 4>::default();
+    assert_eq!(empty.split_first().await, None);
+    assert_eq!(empty.split_last().await, None);
+}
+
+#[tokio::test]
+async fn stats_reports_len_remaining_and_capacity() {
+    let v = MutexedStaticVec::<u32, 4>::default();
+    assert_eq!(v.stats().await, (0, 4, 4));
+    v.push(1).await.unwrap();
+    v.push(2).await.unwrap();
+    v.push(3).await.unwrap();
+    assert_eq!(v.stats().await, (3, 1, 4));
+}
+
+#[tokio::test]
+async fn size_and_used_bytes() {
+    const SIZE: usize = MutexedStaticVec::<u32, 8>::size_bytes();
+    assert_eq!(SIZE, 32);
+
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.used_bytes().await, 12);
+    assert_eq!(MutexedStaticVec::<(), 8>::size_bytes(), 0);
+}
+
+#[tokio::test]
+async fn for_each_rev_walks_backwards() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let mut seen = Vec::new();
+    v.for_each_rev(|&x| seen.push(x)).await;
+    assert_eq!(seen, [3, 2, 1]);
+}
+
+#[tokio::test]
+async fn eq_slice_compares_the_prefix() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    assert!(v.eq_slice(&[1, 2]).await);
+    assert!(!v.eq_slice(&[1, 2, 0]).await);
+    assert!(!v.eq_slice(&[2, 1]).await);
+    assert!(MutexedStaticVec::<u32, 4>::default().eq_slice(&[]).await);
+}
+
+#[tokio::test]
+async fn min_and_max() {
+    let v = MutexedStaticVec::<i32, 4>::try_from_iter([3, -1, 4, 1])
+        .await
+        .unwrap();
+    assert_eq!(v.max().await, Some(&4));
+    assert_eq!(v.min().await, Some(&-1));
+    assert_eq!(v.max_by_key(|x| x.abs() % 4).await, Some(&3));
+    assert_eq!(v.min_by_key(|x| x.abs() % 4).await, Some(&4));
+
+    let empty = MutexedStaticVec::<i32, 4>::default();
+    assert_eq!(empty.max().await, None);
+    assert_eq!(empty.min_by_key(|&x| x).await, None);
+}
+
+#[tokio::test]
+async fn fold_and_sum() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.fold(0, |acc, &x| acc * 10 + x).await, 123);
+    assert_eq!(v.sum().await, 6);
+    assert_eq!(MutexedStaticVec::<u32, 4>::default().sum().await, 0);
+}
+
+#[tokio::test]
+async fn ensure_capacity_checks_the_remaining_room() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.ensure_capacity(1).await, Ok(()));
+    assert_eq!(v.ensure_capacity(0).await, Ok(()));
+    assert_eq!(
+        v.ensure_capacity(2).await,
+        Err(mutexedstaticvec::StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn to_cloned_vec_leaves_the_source() {
+    let mut v = MutexedStaticVec::<String, 4>::try_from_iter(["a".to_string(), "b".to_string()])
+        .await
+        .unwrap();
+    let cloned: Vec<String> = v.to_cloned_vec().await.collect();
+    assert_eq!(cloned, ["a", "b"]);
+    assert_eq!(v.as_slice().await, &["a", "b"]);
+    // the vec has no Drop, so move the strings out to free them
+    drop(v.take_all_into::<Vec<_>>().await);
+}
+
+#[tokio::test]
+async fn is_sorted_variants() {
+    let v = MutexedStaticVec::<i32, 4>::try_from_iter([1, 2, 2, 5])
+        .await
+        .unwrap();
+    assert!(v.is_sorted().await);
+    assert!(!v.is_sorted_by(|a, b| a < b).await);
+    assert!(!v.is_sorted_by_key(|x| -x).await);
+
+    let v = MutexedStaticVec::<i32, 4>::try_from_iter([3, 1])
+        .await
+        .unwrap();
+    assert!(!v.is_sorted().await);
+    assert!(MutexedStaticVec::<i32, 4>::default().is_sorted().await);
+}
+
+#[tokio::test]
+async fn index_checked_reports_the_len() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    assert_eq!(v.index_checked(1).await, Ok(&2));
+    assert_eq!(
+        v.index_checked(2).await,
+        Err(mutexedstaticvec::StaticVecError::OutOfBounds { index: 2, len: 2 })
+    );
+}
+
+#[tokio::test]
+async fn utilization_is_len_over_capacity() {
+    let v = MutexedStaticVec::<u32, 4>::default();
+    assert_eq!(v.utilization().await, 0.0);
+    v.push(1).await.unwrap();
+    assert_eq!(v.utilization().await, 0.25);
+    v.push_many([2, 3, 4]).await.unwrap();
+    assert_eq!(v.utilization().await, 1.0);
+
+    let v = MutexedStaticVec::<u32, 0>::default();
+    assert_eq!(v.utilization().await, 0.0);
+}
+
+#[tokio::test]
+async fn chunks_exact_with_remainder() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter(1..=5)
+        .await
+        .unwrap();
+    let (chunks, remainder) = v.chunks_exact(2).await;
+    assert_eq!(chunks.collect::<Vec<_>>(), [&[1, 2], &[3, 4]]);
+    assert_eq!(remainder, &[5]);
+}
+
+#[tokio::test]
+async fn is_disjoint_against_other_and_self() {
+    let a = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    let b = MutexedStaticVec::<u32, 4>::try_from_iter([3, 4])
+        .await
+        .unwrap();
+    let c = MutexedStaticVec::<u32, 4>::try_from_iter([5, 2])
+        .await
+        .unwrap();
+    assert!(a.is_disjoint(&b).await);
+    assert!(!a.is_disjoint(&c).await);
+    assert!(!a.is_disjoint(&a).await);
+
+    let empty = MutexedStaticVec::<u32, 4>::default();
+    assert!(empty.is_disjoint(&empty).await);
+}
+
+#[tokio::test]
+async fn find_map_returns_the_first_hit() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.find_map(|&x| (x > 1).then_some(x * 10)).await, Some(20));
+    assert_eq!(v.find_map(|&x| (x > 5).then_some(x)).await, None);
+}
+
+#[tokio::test]
+async fn interleave_alternates_then_appends() {
+    let a = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let b = MutexedStaticVec::<u32, 4>::try_from_iter([10, 20])
+        .await
+        .unwrap();
+    let v = a.interleave::<8>(&b).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1, 10, 2, 20, 3]);
+    let v = a.interleave::<8>(&a).await.unwrap();
+    assert_eq!(v.as_slice().await, &[1, 1, 2, 2, 3, 3]);
+    assert_eq!(
+        a.interleave::<4>(&b).await.err(),
+        Some(mutexedstaticvec::StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn limit_caps_the_view() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.limit(2).await, &[1, 2]);
+    assert_eq!(v.limit(10).await, &[1, 2, 3]);
+    assert!(v.limit(0).await.is_empty());
+}
+
+#[tokio::test]
+async fn next_free_until_full() {
+    let v = MutexedStaticVec::<u32, 2>::default();
+    assert_eq!(v.next_free().await, Some(0));
+    v.push(1).await.unwrap();
+    assert_eq!(v.next_free().await, Some(1));
+    v.push(2).await.unwrap();
+    assert_eq!(v.next_free().await, None);
+}
+
+#[tokio::test]
+async fn count_value_tallies_equal_elements() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([1, 2, 1, 3, 1])
+        .await
+        .unwrap();
+    assert_eq!(v.count_value(&1).await, 3);
+    assert_eq!(v.count_value(&3).await, 1);
+    assert_eq!(v.count_value(&4).await, 0);
+}
+
+#[tokio::test]
+async fn take_while_and_skip_while_split_the_prefix() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([1, 2, 5, 1])
+        .await
+        .unwrap();
+    assert_eq!(v.take_while(|&x| x < 3).await, &[1, 2]);
+    assert_eq!(v.skip_while(|&x| x < 3).await, &[5, 1]);
+    assert_eq!(v.take_while(|_| true).await, &[1, 2, 5, 1]);
+    assert!(v.skip_while(|_| true).await.is_empty());
+}
+
+#[tokio::test]
+async fn gather_clones_in_index_order() {
+    let v = MutexedStaticVec::<u32, 3>::try_from_iter([10, 20, 30])
+        .await
+        .unwrap();
+    let picked = v.gather(&[2, 0, 2]).await.unwrap();
+    assert_eq!(picked.as_slice().await, &[30, 10, 30]);
+    assert_eq!(
+        v.gather(&[1, 3]).await.err(),
+        Some(mutexedstaticvec::StaticVecError::OutOfBounds { index: 3, len: 3 })
+    );
+    assert_eq!(
+        v.gather(&[0, 0, 0, 0]).await.err(),
+        Some(mutexedstaticvec::StaticVecError::CapacityExceeded)
+    );
+}
+
+#[tokio::test]
+async fn assert_len_le_accepts_the_bound() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    v.assert_len_le(2).await;
+    v.assert_len_le(4).await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "length 2 exceeds bound 1")]
+async fn assert_len_le_panics_past_the_bound() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2])
+        .await
+        .unwrap();
+    v.assert_len_le(1).await;
+}
+
+#[tokio::test]
+async fn duplicates_are_found_by_first_repeat() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([3, 1, 4, 1, 3])
+        .await
+        .unwrap();
+    assert!(v.has_duplicates().await);
+    assert_eq!(v.first_duplicate().await, Some(3));
+
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert!(!v.has_duplicates().await);
+    assert_eq!(v.first_duplicate().await, None);
+}
+
+#[tokio::test]
+async fn position_max_and_min_report_the_first_tie() {
+    let v = MutexedStaticVec::<u32, 8>::try_from_iter([2, 5, 0, 5, 0])
+        .await
+        .unwrap();
+    assert_eq!(v.position_max().await, Some(1));
+    assert_eq!(v.position_min().await, Some(2));
+
+    let empty = MutexedStaticVec::<u32, 8>::default();
+    assert_eq!(empty.position_max().await, None);
+    assert_eq!(empty.position_min().await, None);
+}
+
+#[tokio::test]
+async fn as_array_only_when_full() {
+    let v = MutexedStaticVec::<u32, 2>::try_from_iter([1])
+        .await
+        .unwrap();
+    assert_eq!(v.as_array().await, None);
+    v.push(2).await.unwrap();
+    assert_eq!(v.as_array().await, Some(&[1, 2]));
+}
+
+#[tokio::test]
+async fn sum_by_projects_each_element() {
+    let v = MutexedStaticVec::<(u32, u8), 4>::try_from_iter([(1, 9), (2, 9), (3, 9)])
+        .await
+        .unwrap();
+    assert_eq!(v.sum_by(|&(x, _)| x).await, 6);
+    assert_eq!(v.sum_by(|&(_, y)| u32::from(y)).await, 27);
+}
+
+#[tokio::test]
+async fn is_valid_index_follows_the_len() {
+    let v = MutexedStaticVec::<u32, 4>::default();
+    assert!(!v.is_valid_index(0).await);
+    v.push(1).await.unwrap();
+    assert!(v.is_valid_index(0).await);
+    assert!(!v.is_valid_index(1).await);
+}
+
+#[tokio::test]
+async fn copy_window_copies_a_full_window_only() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.copy_window::<2>(1).await, Some([2, 3]));
+    assert_eq!(v.copy_window::<2>(2).await, None);
+    assert_eq!(v.copy_window::<0>(3).await, Some([]));
+    assert_eq!(v.copy_window::<0>(4).await, None);
+}
+
+#[tokio::test]
+async fn reversed_leaves_the_source() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.reversed().await.as_slice().await, &[3, 2, 1]);
+    assert_eq!(v.as_slice().await, &[1, 2, 3]);
+}
+
+// runs without and with the alloc feature, covering both comparison paths
+#[tokio::test]
+async fn eq_unordered_compares_multiplicities() {
+    let a = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 2])
+        .await
+        .unwrap();
+    let b = MutexedStaticVec::<u32, 4>::try_from_iter([2, 1, 2])
+        .await
+        .unwrap();
+    let c = MutexedStaticVec::<u32, 4>::try_from_iter([1, 1, 2])
+        .await
+        .unwrap();
+    assert!(a.eq_unordered(&b).await);
+    assert!(a.eq_unordered(&a).await);
+    assert!(!a.eq_unordered(&c).await);
+    assert!(!a.eq_unordered(&MutexedStaticVec::default()).await);
+}
+
+#[tokio::test]
+async fn prefix_sums_accumulate() {
+    let v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(v.prefix_sums().await.as_slice().await, &[1, 3, 6]);
+    assert!(
+        MutexedStaticVec::<u32, 4>::default()
+            .prefix_sums()
+            .await
+            .is_empty()
+            .await
+    );
+}
@@ -0,0 +1,48 @@
+use futures::StreamExt;
+use mutexedstaticvec::MutexedStaticVec;
+
+#[tokio::test]
+async fn poll_recv_backs_a_stream() {
+    let mut v = MutexedStaticVec::<u32, 4>::try_from_iter([1, 2, 3])
+        .await
+        .unwrap();
+    let items: Vec<u32> = futures::stream::poll_fn(|cx| v.poll_recv(cx))
+        .collect()
+        .await;
+    assert_eq!(items, [1, 2, 3]);
+    assert!(v.is_empty().await);
+}
+
+#[tokio::test]
+async fn poll_recv_ends_when_empty() {
+    let mut v = MutexedStaticVec::<u32, 4>::default();
+    let mut recv = futures::stream::poll_fn(|cx| v.poll_recv(cx));
+    assert_eq!(futures::poll!(recv.next()), std::task::Poll::Ready(None));
+    drop(recv);
+
+    v.push(7).await.unwrap();
+    let mut recv = futures::stream::poll_fn(|cx| v.poll_recv(cx));
+    assert_eq!(recv.next().await, Some(7));
+    assert_eq!(recv.next().await, None);
+}
+
+#[tokio::test]
+async fn pop_front_is_fifo() {
+    let mut v = MutexedStaticVec::<u32, 4>::default();
+    v.push_many([1, 2, 3]).await.unwrap();
+    assert_eq!(v.pop_front().await, Some(1));
+    v.push(4).await.unwrap();
+    assert_eq!(v.pop_front().await, Some(2));
+    assert_eq!(v.pop_front().await, Some(3));
+    assert_eq!(v.pop_front().await, Some(4));
+    assert_eq!(v.pop_front().await, None);
+}
+
+#[tokio::test]
+async fn cycle_rotates_the_front_to_the_back() {
+    let mut v = MutexedStaticVec::<u32, 4>::default();
+    assert_eq!(v.cycle().await, None);
+    v.push_many([1, 2, 3]).await.unwrap();
+    assert_eq!(v.cycle().await, Some(&1));
+    assert_eq!(v.as_slice().await, &[2, 3, 1]);
+}
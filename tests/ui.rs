@@ -0,0 +1,9 @@
+// pass cases make trybuild run a full build, so errors raised at monomorphization are caught
+#[test]
+// trybuild spawns cargo, which Miri cannot do
+#[cfg_attr(miri, ignore)]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/*_ok.rs");
+    t.compile_fail("tests/ui/*_fail.rs");
+}
@@ -0,0 +1,5 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+fn main() {
+    let _ = MutexedStaticVec::<u32, 2>::new_const_len::<3>();
+}
@@ -0,0 +1,6 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+fn main() {
+    let _ = MutexedStaticVec::<u32, 2>::new_const_len::<2>();
+    let _ = MutexedStaticVec::<u32, 2>::new_const_len::<0>();
+}
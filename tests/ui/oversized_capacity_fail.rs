@@ -0,0 +1,5 @@
+use mutexedstaticvec::MutexedStaticVec;
+
+fn main() {
+    let _ = MutexedStaticVec::<u64, { usize::MAX / 4 }>::default();
+}
@@ -0,0 +1,262 @@
+use mutexedstaticvec::with_locks::{KeyTrait, OptionMutexTrait, TryLockItemTrait};
+use mutexedstaticvec::MutexedStaticVec;
+use tokio::sync::{Mutex, MutexGuard};
+
+// the key lives in the outer element, the payload behind its own lock
+#[derive(Debug)]
+struct Entry {
+    key: u32,
+    item: Mutex<Option<u32>>,
+}
+
+fn entry(key: u32, item: u32) -> Entry {
+    Entry {
+        key,
+        item: Mutex::new(Some(item)),
+    }
+}
+
+impl KeyTrait for Entry {
+    type Key = u32;
+
+    fn get_key(&self) -> u32 {
+        self.key
+    }
+}
+
+impl<'a> OptionMutexTrait<'a> for Entry {
+    type Item = u32;
+    type ItemMutex = Mutex<Option<u32>>;
+    type Guard = MutexGuard<'a, Option<u32>>;
+
+    fn get_item_lock(&'a self) -> &'a Self::ItemMutex {
+        &self.item
+    }
+
+    async fn lock_item(&'a self) -> Self::Guard {
+        self.item.lock().await
+    }
+
+    async fn set_item(&self, val: Option<u32>) {
+        *self.item.lock().await = val;
+    }
+
+    async fn take_item(&self) -> Option<u32> {
+        self.item.lock().await.take()
+    }
+}
+
+impl<'a> TryLockItemTrait<'a> for Entry {
+    fn try_lock_item(&'a self) -> Option<Self::Guard> {
+        self.item.try_lock().ok()
+    }
+}
+
+async fn table<const N: usize>(keys: &[u32]) -> MutexedStaticVec<Entry, N> {
+    MutexedStaticVec::try_from_iter(keys.iter().map(|&key| entry(key, key * 10)))
+        .await
+        .unwrap()
+}
+
+// (key, item) pairs in slot order
+async fn pairs<const N: usize>(v: &MutexedStaticVec<Entry, N>) -> Vec<(u32, Option<u32>)> {
+    let mut ret = Vec::new();
+    for x in v.iter().await {
+        ret.push((x.key, *x.item.lock().await));
+    }
+    ret
+}
+
+#[tokio::test]
+async fn retain_keeps_matching_entries_with_their_items() {
+    use mutexedstaticvec::with_locks::RetainWithLocksTrait;
+
+    let mut v = table::<4>(&[0, 1, 2, 3]).await;
+    v.retain_with_locks(|&k| k == 1 || k == 2, |_| true).await;
+    let mut kept = pairs(&v).await;
+    kept.sort();
+    assert_eq!(kept, [(1, Some(10)), (2, Some(20))]);
+
+    let mut v = table::<4>(&[0, 1, 2, 3]).await;
+    v.retain_with_locks(|&k| k != 0, |&item| item != 30).await;
+    let mut kept = pairs(&v).await;
+    kept.sort();
+    assert_eq!(kept, [(1, Some(10)), (2, Some(20))]);
+}
+
+#[tokio::test]
+async fn drain_by_key_packs_survivors_with_their_items() {
+    use mutexedstaticvec::with_locks::DrainByKeyTrait;
+
+    let mut v = table::<4>(&[0, 1, 2, 3]).await;
+    assert_eq!(v.drain_by_key(|&k| k % 2 == 0).await, 2);
+    assert_eq!(pairs(&v).await, [(1, Some(10)), (3, Some(30))]);
+    assert_eq!(v.drain_by_key(|&k| k > 5).await, 0);
+}
+
+#[tokio::test]
+async fn promote_moves_the_entry_to_the_front() {
+    use mutexedstaticvec::with_locks::{KeyIndexTrait, PromoteWithLocksTrait};
+
+    let mut v = table::<4>(&[0, 1, 2, 3]).await;
+    assert!(v.promote_with_locks(|&k| k == 2).await);
+    assert_eq!(v.key_index(|&k| k == 2).await, Some(0));
+    assert_eq!(
+        pairs(&v).await,
+        [(2, Some(20)), (0, Some(0)), (1, Some(10)), (3, Some(30))]
+    );
+    assert!(!v.promote_with_locks(|&k| k == 9).await);
+}
+
+#[tokio::test]
+async fn rebalance_sorts_entries_with_their_items() {
+    use mutexedstaticvec::with_locks::{
+        InsertWithLocksTrait, RebalanceWithLocksTrait, RemoveWithLocksTrait,
+    };
+
+    let mut v = table::<8>(&[3, 1, 2, 0]).await;
+    v.rebalance_with_locks().await;
+    assert_eq!(
+        pairs(&v).await,
+        [(0, Some(0)), (1, Some(10)), (2, Some(20)), (3, Some(30))]
+    );
+
+    v.insert_with_locks(entry(1, 11)).await.unwrap();
+    v.insert_with_locks(entry(5, 50)).await.unwrap();
+    assert!(v.remove_with_locks(|&k| k == 0, |_| true).await);
+    v.rebalance_with_locks().await;
+    let keys: Vec<u32> = v.iter().await.map(|x| x.key).collect();
+    assert!(keys.is_sorted());
+}
+
+#[tokio::test]
+async fn key_index_finds_the_first_match() {
+    use mutexedstaticvec::with_locks::KeyIndexTrait;
+
+    let v = table::<4>(&[4, 7, 7]).await;
+    assert_eq!(v.key_index(|&k| k == 7).await, Some(1));
+    assert_eq!(v.key_index(|&k| k == 4).await, Some(0));
+    assert_eq!(v.key_index(|&k| k == 9).await, None);
+}
+
+#[tokio::test]
+async fn insert_with_locks_appends_until_full() {
+    use mutexedstaticvec::with_locks::InsertWithLocksTrait;
+    use mutexedstaticvec::StaticVecError;
+
+    let v = table::<3>(&[1]).await;
+    assert_eq!(v.insert_with_locks(entry(2, 20)).await, Ok(1));
+    assert_eq!(v.insert_with_locks(entry(3, 30)).await, Ok(2));
+    assert_eq!(
+        v.insert_with_locks(entry(4, 40)).await,
+        Err(StaticVecError::CapacityExceeded)
+    );
+    assert_eq!(
+        pairs(&v).await,
+        [(1, Some(10)), (2, Some(20)), (3, Some(30))]
+    );
+}
+
+#[tokio::test]
+async fn try_remove_with_locks_never_waits() {
+    use mutexedstaticvec::with_locks::{TryRemoveResult, TryRemoveWithLocksTrait};
+
+    // a forgotten guard keeps the item locked without borrowing the vec
+    let mut v = table::<4>(&[1]).await;
+    std::mem::forget(v.iter().await.next().unwrap().item.lock().await);
+    assert_eq!(
+        v.try_remove_with_locks(|&k| k == 1, |_| true),
+        TryRemoveResult::WouldBlock
+    );
+
+    let mut v = table::<4>(&[1, 2, 3]).await;
+    assert_eq!(
+        v.try_remove_with_locks(|&k| k == 9, |_| true),
+        TryRemoveResult::NotFound
+    );
+    assert_eq!(
+        v.try_remove_with_locks(|&k| k == 1, |&item| item == 0),
+        TryRemoveResult::NotFound
+    );
+    // a non-last entry is replaced by the last one, key and item together
+    assert_eq!(
+        v.try_remove_with_locks(|&k| k == 1, |&item| item == 10),
+        TryRemoveResult::Removed
+    );
+    assert_eq!(pairs(&v).await, [(3, Some(30)), (2, Some(20))]);
+    assert_eq!(
+        v.try_remove_with_locks(|&k| k == 2, |_| true),
+        TryRemoveResult::Removed
+    );
+    assert_eq!(pairs(&v).await, [(3, Some(30))]);
+}
+
+#[tokio::test]
+async fn remove_with_locks_reports_the_removed_key() {
+    use mutexedstaticvec::with_locks::RemoveWithLocksTrait;
+
+    let mut v = table::<4>(&[1, 2, 3]).await;
+    assert_eq!(
+        v.remove_with_locks_returning_key(|&k| k == 3, |_| true)
+            .await,
+        Some(3)
+    );
+    assert_eq!(
+        v.remove_with_locks_returning_key(|&k| k == 2, |&item| item == 0)
+            .await,
+        None
+    );
+    assert_eq!(
+        v.remove_with_locks_returning_key(|&k| k == 9, |_| true)
+            .await,
+        None
+    );
+    assert!(v.remove_with_locks(|&k| k == 2, |_| true).await);
+    assert_eq!(pairs(&v).await, [(1, Some(10))]);
+}
+
+#[tokio::test]
+async fn remove_with_locks_moves_the_last_entry_whole() {
+    use mutexedstaticvec::with_locks::{KeyIndexTrait, RemoveWithLocksTrait};
+
+    let mut v = table::<4>(&[1, 2, 3]).await;
+    assert_eq!(
+        v.remove_with_locks_returning_key(|&k| k == 1, |_| true)
+            .await,
+        Some(1)
+    );
+    assert_eq!(pairs(&v).await, [(3, Some(30)), (2, Some(20))]);
+    assert_eq!(v.key_index(|&k| k == 1).await, None);
+    assert_eq!(v.key_index(|&k| k == 3).await, Some(0));
+}
+
+#[tokio::test]
+async fn get_by_key_cloned_releases_the_inner_lock() {
+    use mutexedstaticvec::with_locks::GetByKeyClonedTrait;
+
+    let v = table::<4>(&[1, 2]).await;
+    assert_eq!(v.get_by_key_cloned(|&k| k == 2).await, Some(20));
+    assert_eq!(v.get_by_key_cloned(|&k| k == 9).await, None);
+    assert!(v.iter().await.all(|x| x.item.try_lock().is_ok()));
+
+    v.iter().await.next().unwrap().item.lock().await.take();
+    assert_eq!(v.get_by_key_cloned(|&k| k == 1).await, None);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn items_stream_yields_keys_with_locked_items() {
+    use futures::StreamExt;
+    use mutexedstaticvec::with_locks::ItemsStreamTrait;
+
+    let v = table::<4>(&[1, 2, 3]).await;
+    let mut stream = std::pin::pin!(v.items_stream());
+    let (key, mut guard) = stream.next().await.unwrap();
+    assert_eq!((key, *guard), (1, Some(10)));
+    *guard = Some(11);
+    drop(guard);
+
+    let rest: Vec<(u32, Option<u32>)> = stream.map(|(key, guard)| (key, *guard)).collect().await;
+    assert_eq!(rest, [(2, Some(20)), (3, Some(30))]);
+    assert_eq!(pairs(&v).await[0], (1, Some(11)));
+}
@@ -0,0 +1,37 @@
+// zero-sized elements move no bytes, so only the length tracking is exercised; run these under
+// `cargo +nightly miri test --test zst` to check the pointer paths
+use mutexedstaticvec::{MutexedStaticVec, StaticVecError};
+
+#[tokio::test]
+async fn push_remove_iterate_units() {
+    let mut v = MutexedStaticVec::<(), 4>::default();
+    for _ in 0..4 {
+        v.push(()).await.unwrap();
+    }
+    assert_eq!(v.push(()).await, Err(StaticVecError::CapacityExceeded));
+    assert_eq!(v.as_slice().await.len(), 4);
+
+    v.remove(1).await;
+    v.swap_remove(0).await;
+    assert_eq!(v.len().await, 2);
+    assert_eq!(v.iter().await.count(), 2);
+
+    v.retain(|_| false).await;
+    assert!(v.as_slice().await.is_empty());
+}
+
+#[tokio::test]
+async fn guard_and_bulk_paths_on_units() {
+    let mut v = MutexedStaticVec::<(), 8>::try_from_iter([(); 3])
+        .await
+        .unwrap();
+    assert_eq!(v.push_many([(); 3]).await, Ok(3));
+    {
+        let mut guard = v.lock_mut().await;
+        assert_eq!(guard.pop(), Some(()));
+        guard.remove(0);
+        assert_eq!(guard.len(), 4);
+    }
+    assert_eq!(v.to_cloned_vec().await.count(), 4);
+    assert_eq!(v.reversed().await.len().await, 4);
+}